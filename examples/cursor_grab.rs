@@ -1,7 +1,7 @@
 use winit::{
     event::{DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    window::{CursorGrabMode, WindowBuilder},
 };
 
 fn main() {
@@ -33,7 +33,16 @@ fn main() {
                     use winit::event::VirtualKeyCode::*;
                     match key {
                         Escape => *control_flow = ControlFlow::Exit,
-                        G => window.set_cursor_grab(!modifiers.shift()).unwrap(),
+                        G | L => {
+                            let mode = match (key, modifiers.shift()) {
+                                (_, true) => CursorGrabMode::None,
+                                (G, false) => CursorGrabMode::Confined,
+                                _ => CursorGrabMode::Locked,
+                            };
+                            if let Err(err) = window.set_cursor_grab(mode) {
+                                println!("error: {}", err);
+                            }
+                        }
                         H => window.set_cursor_visible(modifiers.shift()),
                         _ => (),
                     }
@@ -6,7 +6,7 @@ fn main() {
         dpi::{PhysicalPosition, PhysicalSize, Position, Size},
         event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
         event_loop::{ControlFlow, EventLoop},
-        window::{CursorIcon, Fullscreen, WindowBuilder},
+        window::{CursorGrabMode, CursorIcon, Fullscreen, WindowBuilder},
     };
 
     const WINDOW_COUNT: usize = 3;
@@ -90,7 +90,21 @@ fn main() {
                                 )),
                                 (false, _) => None,
                             }),
-                            G => window.set_cursor_grab(state).unwrap(),
+                            G => {
+                                // Not every platform can confine the cursor, so fall back to
+                                // locking it
+                                let result = match state {
+                                    true => {
+                                        window.set_cursor_grab(CursorGrabMode::Confined).or_else(
+                                            |_| window.set_cursor_grab(CursorGrabMode::Locked),
+                                        )
+                                    }
+                                    false => window.set_cursor_grab(CursorGrabMode::None),
+                                };
+                                if let Err(err) = result {
+                                    println!("error: {}", err);
+                                }
+                            }
                             H => window.set_cursor_visible(!state),
                             I => {
                                 println!("Info:");
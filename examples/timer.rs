@@ -1,8 +1,7 @@
-use instant::Instant;
 use std::time::Duration;
 use winit::{
     event::{Event, StartCause, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
+    event_loop::{ControlFlow, EventLoop, Instant},
     window::WindowBuilder,
 };
 
@@ -39,7 +39,7 @@ use std::path::PathBuf;
 use crate::{
     dpi::{LogicalPosition, PhysicalPosition, PhysicalSize},
     platform_impl,
-    window::{Theme, WindowId},
+    window::{Orientation, Theme, WindowId},
 };
 
 /// Describes a generic event.
@@ -71,9 +71,18 @@ pub enum Event<T: 'static> {
     UserEvent(T),
 
     /// Emitted when the application has been suspended.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted when the page is frozen, or kept in the back/forward cache when it is left.
     Suspended,
 
     /// Emitted when the application has been resumed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted once the event loop has started, and when a suspended page is shown
+    ///   again.
     Resumed,
 
     /// Emitted when all of the event loop's input events have been processed and redraw processing
@@ -165,9 +174,19 @@ pub enum WindowEvent {
     Resized(PhysicalSize<u32>),
 
     /// The position of the window has changed. Contains the window's new position.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The position of the canvas in the viewport, which changes when the page is
+    ///   scrolled. Coordinates left or above the viewport are clamped to zero.
     Moved(PhysicalPosition<u32>),
 
     /// The window has been requested to close.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted when the user is about to leave the page, which can't be prevented
+    ///   without `WindowExtWebSys::set_unload_confirmation`.
     CloseRequested,
 
     /// The window has been destroyed.
@@ -177,12 +196,21 @@ pub enum WindowEvent {
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The path only contains the name of the file. Use
+    ///   `WindowExtWebSys::dropped_file` to access its content.
     DroppedFile(PathBuf),
 
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The path is always empty, as browsers don't expose the names of dragged files.
     HoveredFile(PathBuf),
 
     /// A file was hovered, but has exited the window.
@@ -262,6 +290,39 @@ pub enum WindowEvent {
         stage: i64,
     },
 
+    /// Pinch-to-zoom gesture.
+    ///
+    /// `delta` is the change of the magnification since the previous event, relative to it, e.g.
+    /// 0.1 when the distance between the fingers grew by 10%. Positive values zoom in.
+    ///
+    /// At the moment this is only supported on the web, where it is derived from two touch points
+    /// moving on a touch screen, or reported by Safari for trackpads.
+    TouchpadMagnify {
+        device_id: DeviceId,
+        delta: f64,
+        phase: TouchPhase,
+    },
+
+    /// Two-finger rotation gesture.
+    ///
+    /// `delta` is the change of the angle since the previous event in degrees. Positive values
+    /// rotate counterclockwise.
+    ///
+    /// At the moment this is only supported on the web, where it is derived from two touch points
+    /// moving on a touch screen, or reported by Safari for trackpads.
+    TouchpadRotate {
+        device_id: DeviceId,
+        delta: f64,
+        phase: TouchPhase,
+    },
+
+    /// Smart magnification gesture, which asks to zoom in on the content under it, or back out.
+    ///
+    /// At the moment this is only supported on the web, where it is reported for a double tap: two
+    /// quick taps of a single finger close to each other on a touch screen. The `Touch` events of
+    /// both taps are still reported, and the second tap is followed by this event.
+    SmartMagnify { device_id: DeviceId },
+
     /// Motion on some analog axis. May report data redundant to other, more specific events.
     AxisMotion {
         device_id: DeviceId,
@@ -280,11 +341,16 @@ pub enum WindowEvent {
     /// * Changing the display's scale factor (e.g. in Control Panel on Windows).
     /// * Moving the window to a display with a different scale factor.
     ///
-    /// After this event callback has been processed, the window will be resized to whatever value
-    /// is pointed to by the `new_inner_size` reference. By default, this will contain the size suggested
-    /// by the OS, but it can be changed to any value.
+    /// After this event callback has been processed, the window will be resized to
+    /// `new_inner_size`, the size suggested by the OS.
     ///
     /// For more information about DPI in general, see the [`dpi`](crate::dpi) module.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Calling `Window::set_inner_size` while handling this event replaces the suggested
+    ///   size, which is applied once the handler returns. `Resized` is then emitted for the size the
+    ///   canvas ends up with.
     ScaleFactorChanged {
         scale_factor: f64,
         new_inner_size: PhysicalSize<u32>,
@@ -295,8 +361,50 @@ pub enum WindowEvent {
     /// Applications might wish to react to this to change the theme of the content of the window
     /// when the system changes the window theme.
     ///
-    /// At the moment this is only supported on Windows.
+    /// At the moment this is only supported on Windows and the web.
     ThemeChanged(Theme),
+
+    /// The text content of the clipboard has been received.
+    ///
+    /// This is emitted in response to a request to read the clipboard. At the moment this is only
+    /// supported on the web, see `platform::web::Clipboard`.
+    ReceivedClipboardText(String),
+
+    /// Reading the text content of the clipboard has failed, e.g. because the user denied the
+    /// permission to read it, or the clipboard holds no text.
+    ///
+    /// This is emitted instead of `ReceivedClipboardText` in response to a request to read the
+    /// clipboard. At the moment this is only supported on the web.
+    ClipboardReadFailed,
+
+    /// The window has been occluded (completely hidden from view) or is no longer occluded.
+    ///
+    /// At the moment this is only supported on the web, where it reports whether the page is
+    /// hidden, e.g. when its tab is in the background, or the window was hidden with
+    /// `Window::set_visible`.
+    Occluded(bool),
+
+    /// An input method produced or committed text.
+    ///
+    /// At the moment this is only supported on the web.
+    Ime(Ime),
+
+    /// The graphics context of the window has been lost, e.g. because the GPU was reset.
+    ///
+    /// All graphics resources have to be recreated once `ContextRestored` is received. At the
+    /// moment this is only supported on the web, where it reports `webglcontextlost`.
+    ContextLost,
+
+    /// The graphics context of the window has been restored after a `ContextLost`.
+    ///
+    /// At the moment this is only supported on the web, where it reports `webglcontextrestored`.
+    ContextRestored,
+
+    /// The orientation of the screen the window is displayed on has changed.
+    ///
+    /// At the moment this is only supported on the web, see
+    /// `platform::web::WindowExtWebSys::lock_orientation`.
+    OrientationChanged(Orientation),
 }
 
 /// Identifier of an input device.
@@ -403,6 +511,26 @@ pub struct KeyboardInput {
     pub modifiers: ModifiersState,
 }
 
+/// Describes a change in the state of an input method editor (IME) composition.
+///
+/// A composition begins with a `Preedit` event, is updated by further `Preedit` events, and ends
+/// when the preedit text is cleared, which is followed by a `Commit` unless the composition was
+/// cancelled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Ime {
+    /// The text being composed has changed.
+    ///
+    /// `cursor_range` is the byte range of the cursor inside the text, if known. An empty `text`
+    /// means the composition has ended.
+    Preedit {
+        text: String,
+        cursor_range: Option<(usize, usize)>,
+    },
+    /// The composition has been completed and the text should be inserted.
+    Commit(String),
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -439,8 +567,16 @@ pub struct Touch {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **iOS** 9.0+ and **Windows** 8+.
+    /// - Only available on **iOS** 9.0+, **Windows** 8+ and **Web**. Browsers without pressure
+    ///   support report a force of `0.0` on the **Web**.
     pub force: Option<Force>,
+    /// The radii of the ellipse that most closely circumscribes the area of contact, along the x
+    /// and y axes. May be `None` if the platform does not report the area of contact.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Web** browsers reporting the radius of touches.
+    pub radius: Option<PhysicalSize<f64>>,
     /// Unique identifier of a finger.
     pub id: u64,
 }
@@ -525,6 +661,12 @@ pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// Any other button, numbered by the platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Web:** The back and forward buttons are `Other(1)` and `Other(2)`.
+    /// - **X11:** The back and forward buttons are `Other(8)` and `Other(9)`.
     Other(u8),
 }
 
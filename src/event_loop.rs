@@ -9,12 +9,17 @@
 //! [create_proxy]: crate::event_loop::EventLoop::create_proxy
 //! [event_loop_proxy]: crate::event_loop::EventLoopProxy
 //! [send_event]: crate::event_loop::EventLoopProxy::send_event
-use instant::Instant;
 use std::ops::Deref;
 use std::{error, fmt};
 
 use crate::{event::Event, monitor::MonitorHandle, platform_impl};
 
+/// A monotonic clock, used by `ControlFlow::WaitUntil` and `StartCause`.
+///
+/// This is `std::time::Instant` on every platform but the web, where that panics. There it is
+/// backed by `performance.now()` instead, so timers written against this type work everywhere.
+pub use instant::Instant;
+
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
 ///
@@ -198,7 +203,19 @@ impl<T: 'static> EventLoopProxy<T> {
     /// `UserEvent(event)` event in the event loop, where `event` is the value passed to this
     /// function.
     ///
-    /// Returns an `Err` if the associated `EventLoop` no longer exists.
+    /// Returns an `Err` if the associated `EventLoop` no longer exists. On Web, the same error is
+    /// returned if too many events are queued.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Events are handled in the order they were sent, along with the events of the
+    ///   page, and an event loop waiting with `ControlFlow::Wait` is woken up immediately.
+    ///   Once the event loop has exited, including while `LoopDestroyed` is handled, or was
+    ///   dropped without running, the event is returned in the `Err`. Events sent before then but
+    ///   not handled yet are discarded.
+    ///   Events sent while the event loop is busy, e.g. from within the event handler, are queued
+    ///   until it returns; once 4096 events sent through proxies are waiting to be handled, the
+    ///   event is returned in the `Err` as well, although the event loop still exists.
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         self.event_loop_proxy.send_event(event)
     }
@@ -212,6 +229,10 @@ impl<T: 'static> fmt::Debug for EventLoopProxy<T> {
 
 /// The error that is returned when an `EventLoopProxy` attempts to wake up an `EventLoop` that
 /// no longer exists. Contains the original event given to `send_event`.
+///
+/// On Web, it is also returned while the event loop still exists, if too many events sent through
+/// proxies are waiting to be handled already. There is no separate error for this, so that the
+/// error type is the same on every platform.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EventLoopClosed<T>(pub T);
 
@@ -131,7 +131,8 @@ impl MonitorHandle {
     ///
     /// ## Platform-specific
     ///
-    /// - **Web:** Always returns (0,0)
+    /// - **Web:** Returns the size of the screen the page is displayed on, which is
+    ///   `screen.width` and `screen.height` scaled by the device pixel ratio.
     #[inline]
     pub fn size(&self) -> PhysicalSize<u32> {
         self.inner.size()
@@ -156,7 +157,7 @@ impl MonitorHandle {
     ///
     /// - **X11:** Can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
     /// - **Android:** Always returns 1.0.
-    /// - **Web:** Always returns 1.0
+    /// - **Web:** Returns the device pixel ratio.
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.inner.scale_factor()
@@ -166,7 +167,8 @@ impl MonitorHandle {
     ///
     /// ## Platform-specific
     ///
-    /// - **Web:** Always returns an empty iterator
+    /// - **Web:** Returns a single video mode of the current resolution, as it can't be changed.
+    ///   Its refresh rate is always 60, as browsers don't expose the actual one.
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
         self.inner.video_modes()
@@ -1,7 +1,12 @@
 #![cfg(any(
     target_os = "windows",
     target_os = "macos",
-    target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    feature = "headless"
 ))]
 
 use crate::{
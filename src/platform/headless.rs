@@ -0,0 +1,37 @@
+#![cfg(feature = "headless")]
+
+//! The headless backend replaces the backend of the target platform when the `headless` feature
+//! is enabled. It doesn't connect to any windowing system, so that applications can be run in
+//! tests or on CI machines without a display.
+//!
+//! Windows only keep the state set through their methods, and no events are generated by the
+//! backend itself besides the ones driving the event loop (`NewEvents`, `MainEventsCleared`,
+//! `RedrawRequested`, `RedrawEventsCleared` and `LoopDestroyed`). Events pushed with
+//! `EventLoopWindowTargetExtHeadless::push_event` and sent through an `EventLoopProxy` are
+//! dispatched in the order they arrived, making the loop deterministic.
+//!
+//! Unlike on other platforms, `EventLoop::run` returns once `ControlFlow::Exit` is set.
+//! `EventLoopExtDesktop::run_return` is available as well.
+
+use crate::{event::Event, event_loop::EventLoopWindowTarget};
+
+/// Additional methods on `EventLoopWindowTarget` that are specific to the headless backend.
+pub trait EventLoopWindowTargetExtHeadless {
+    /// A type provided by the user that can be passed through `Event::UserEvent`.
+    type UserEvent;
+
+    /// Pushes an event into the queue of the event loop.
+    ///
+    /// The event is dispatched within the current iteration of the event loop if it is pushed
+    /// while events are being dispatched, and during the next one otherwise. Since `EventLoop`
+    /// dereferences to `EventLoopWindowTarget`, events can also be pushed before it runs.
+    fn push_event(&self, event: Event<Self::UserEvent>);
+}
+
+impl<T> EventLoopWindowTargetExtHeadless for EventLoopWindowTarget<T> {
+    type UserEvent = T;
+
+    fn push_event(&self, event: Event<Self::UserEvent>) {
+        self.p.push_event(event)
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg(target_os = "macos")]
+#![cfg(all(target_os = "macos", not(feature = "headless")))]
 
 use std::os::raw::c_void;
 
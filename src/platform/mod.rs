@@ -9,9 +9,11 @@
 //!  - `windows`
 //!  - `web`
 //!
+//! Or, when the `headless` feature is enabled, the `headless` module instead.
+//!
 //! And the following platform-specific module:
 //!
-//! - `desktop` (available on `windows`, `unix`, and `macos`)
+//! - `desktop` (available on `windows`, `unix`, `macos` and `headless`)
 //!
 //! However only the module corresponding to the platform you're compiling to will be available.
 
@@ -22,4 +24,5 @@ pub mod unix;
 pub mod windows;
 
 pub mod desktop;
+pub mod headless;
 pub mod web;
@@ -1,46 +1,589 @@
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", not(feature = "headless")))]
 
 //! The web target does not automatically insert the canvas element object into the web page, to
-//! allow end users to determine how the page should be laid out. Use the `WindowExtStdweb` or
-//! `WindowExtWebSys` traits (depending on your web backend) to retrieve the canvas from the
-//! Window. Alternatively, use the `WindowBuilderExtStdweb` or `WindowBuilderExtWebSys` to provide
-//! your own canvas.
+//! allow end users to determine how the page should be laid out. Use the `WindowExtWebSys` trait
+//! to retrieve the canvas from the Window. Alternatively, use the `WindowBuilderExtWebSys` to
+//! provide your own canvas.
+//!
+//! Several event loops can be created and run side by side, e.g. to isolate tests. Each one has its
+//! own windows, listeners and animation frames, and only dispatches the events of its own windows.
+//! Window ids are unique across event loops. The title of the page is shared, and set by the
+//! window of any event loop that was focused or given a title last.
+//!
+//! The `DeviceId` of a pointer event is derived from the browser's pointer id and the window whose
+//! canvas received the event. It stays the same for as long as the browser keeps reporting the
+//! same pointer id, which for a mouse is usually the lifetime of the page, but for pen contacts may
+//! only be the duration of a single contact. The same physical pointer therefore has a different
+//! `DeviceId` on each canvas. Touches share one `DeviceId` per canvas. `DeviceIdExtWebSys` tells
+//! which kind of pointer a `DeviceId` belongs to.
+//!
+//! Text composed with dead keys, like `´` followed by `e`, is reported like any other composition,
+//! as `Ime::Preedit` while the accent is pending and `Ime::Commit` with the composed character,
+//! without `WindowEvent::ReceivedCharacter`. While the IME is not allowed, the composed character
+//! is reported as a single `ReceivedCharacter` instead.
+//!
+//! Text inserted without typing, e.g. from an emoji picker or by pasting, is reported whole as a
+//! single `Ime::Commit`, so that characters made up of several code points stay intact. This
+//! requires the IME to be allowed.
+//!
+//! The back and forward buttons of a mouse are reported as `MouseButton::Other(1)` and
+//! `MouseButton::Other(2)`, like on Windows, and any further buttons as `Other(3)` and up.
+//!
+//! Pens are reported like a mouse, and additionally with `WindowEvent::Touch` while they touch the
+//! screen, whose `Force` holds the pressure and the altitude of the pen. Along with them,
+//! `WindowEvent::AxisMotion` is emitted whenever the pressure or the tilt of the pen changed,
+//! with the pressure from 0 to 1 on axis `0`, and the tilt along the x and y axes, from -90 to 90
+//! degrees, on axes `1` and `2`.
 
 use crate::window::WindowBuilder;
 
-#[cfg(feature = "stdweb")]
-use stdweb::web::html_element::CanvasElement;
+#[cfg(feature = "web-sys")]
+use crate::event::DeviceId;
 
-#[cfg(feature = "stdweb")]
-pub trait WindowExtStdweb {
-    fn canvas(&self) -> CanvasElement;
-}
+#[cfg(feature = "web-sys")]
+use crate::window::{Icon, WindowId};
+
+#[cfg(feature = "web-sys")]
+use crate::window::{Orientation, Theme};
+
+#[cfg(feature = "web-sys")]
+use wasm_bindgen::JsValue;
+#[cfg(feature = "web-sys")]
+use web_sys::{File, HtmlCanvasElement, HtmlElement, Node, Worker};
+
+#[cfg(feature = "web-sys")]
+use std::path::Path;
 
 #[cfg(feature = "web-sys")]
-use web_sys::HtmlCanvasElement;
+use crate::{
+    dpi::{PhysicalPosition, PhysicalSize, Size},
+    error::ExternalError,
+    event::{Event, ModifiersState, VirtualKeyCode},
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    platform_impl,
+};
 
 #[cfg(feature = "web-sys")]
 pub trait WindowExtWebSys {
+    /// Returns the canvas element of this window, e.g. to create a rendering context on it.
+    ///
+    /// This is the element the window's event listeners are attached to, and the one identified by
+    /// the `data-raw-handle` attribute matching the id of the window's `RawWindowHandle`. Other
+    /// data attributes can be set and read through its `dataset`, e.g. to share state with
+    /// JavaScript code.
     fn canvas(&self) -> HtmlCanvasElement;
+
+    /// Returns a handle to the system clipboard, through the browser's asynchronous Clipboard API.
+    fn clipboard(&self) -> Clipboard;
+
+    /// Returns a file of the last drop onto the canvas.
+    ///
+    /// Browsers don't expose the location of dropped files, so the path of a
+    /// `WindowEvent::DroppedFile` event is only the name of the file. Passing it to this method
+    /// gives access to the file's content.
+    fn dropped_file(&self, path: &Path) -> Option<File>;
+
+    /// Overrides the scale factor of the window, which is the device pixel ratio by default.
+    ///
+    /// The drawing buffer of the canvas is resized to keep its size on the page, and a
+    /// `WindowEvent::ScaleFactorChanged` event is emitted right away. Passing `None` reverts to
+    /// the device pixel ratio.
+    fn set_scale_factor_override(&self, scale_factor_override: Option<f64>);
+
+    /// Returns the color scheme preferred by the user, from the `prefers-color-scheme` media
+    /// query. Changes are reported with `WindowEvent::ThemeChanged`.
+    fn theme(&self) -> Theme;
+
+    /// Returns the visibility of the page, as reported by the Page Visibility API. Changes between
+    /// visible and hidden are reported with `WindowEvent::Occluded`.
+    fn visibility_state(&self) -> VisibilityState;
+
+    /// Returns the position of the most recent `WindowEvent::CursorMoved` of this window, or
+    /// `None` if the cursor is not over the canvas.
+    fn cursor_position(&self) -> Option<PhysicalPosition<f64>>;
+
+    /// Locks the orientation of the screen, through the Screen Orientation API. Changes of the
+    /// orientation are reported with `WindowEvent::OrientationChanged`.
+    ///
+    /// Most browsers only allow locking the orientation while in fullscreen, so this fails with
+    /// `ExternalError::NotSupported` otherwise, or if the API is not available. Browsers may still
+    /// refuse the lock later on, which is logged.
+    fn lock_orientation(&self, lock: OrientationLock) -> Result<(), ExternalError>;
+
+    /// Unlocks the orientation of the screen, after `lock_orientation`.
+    fn unlock_orientation(&self);
+
+    /// Captures the given keys through the Keyboard Lock API, so that they are delivered as
+    /// `WindowEvent::KeyboardInput` instead of being handled by the browser, e.g. `Escape` to open
+    /// a menu rather than exiting fullscreen. The keys are given by their `KeyboardEvent.code`,
+    /// like `"Escape"` or `"F11"`, and all keys are captured if none are given.
+    ///
+    /// Browsers only allow the lock while in fullscreen, so this fails with
+    /// `ExternalError::NotSupported` otherwise, or if the API is not available. The lock is
+    /// released when leaving fullscreen. Holding `Escape` still exits fullscreen.
+    fn lock_keyboard(&self, codes: &[&str]) -> Result<(), ExternalError>;
+
+    /// Releases the keys captured by `lock_keyboard`.
+    fn unlock_keyboard(&self);
+
+    /// Shows the canvas of a window built with `WindowBuilderExtWebSys::with_hidden_until_ready`,
+    /// e.g. once its first frame was drawn outside of `Event::RedrawRequested`.
+    fn ready(&self);
+
+    /// Flashes the title of the page, alternating between the title of the window prefixed with
+    /// `(*)` and the title the page shows otherwise, until the canvas is focused. Does nothing if
+    /// the canvas is focused already, in a page that has the focus.
+    fn request_user_attention(&self);
+
+    /// Sets the `tabindex` of the canvas, which decides whether and in which order it is focused
+    /// through sequential keyboard navigation. `None` removes it, so that the canvas can't be
+    /// focused at all and gets no keyboard input, and unfocuses it.
+    fn set_tab_index(&self, tab_index: Option<i32>);
+
+    /// Whether the browser asks the user to confirm leaving the page, e.g. while there are unsaved
+    /// changes. `WindowEvent::CloseRequested` is emitted before the browser asks, and the event
+    /// loop only exits once the page is left.
+    ///
+    /// Browsers only ask if the user has interacted with the page, and show a message of their
+    /// own. Disabled by default.
+    fn set_unload_confirmation(&self, confirm: bool);
+
+    /// Whether the drawing buffer of the canvas is resized to match the size it is displayed at,
+    /// when entering or exiting fullscreen and when the scale factor changes.
+    ///
+    /// When disabled, the `width` and `height` of the canvas are only changed by
+    /// `Window::set_inner_size`, e.g. for rendering at a fixed resolution. `WindowEvent::Resized`
+    /// then reports the size the canvas is displayed at, and `WindowEvent::ScaleFactorChanged`
+    /// suggests a size without applying it. Enabled by default.
+    fn set_auto_size(&self, auto_size: bool);
+
+    /// Sets the size the canvas is displayed at on the page, through its CSS size, without
+    /// resizing its drawing buffer like `Window::set_inner_size` does.
+    ///
+    /// With automatic sizing, the drawing buffer is resized to match right away, so it has to be
+    /// disabled with `set_auto_size` for the sizes to differ, e.g. to render at twice the
+    /// resolution the canvas is displayed at.
+    fn set_display_size(&self, size: Size);
+
+    /// Sets the size of the drawing buffer of the canvas, which is stretched to the size the
+    /// canvas is displayed at, without changing its CSS size like `Window::set_inner_size` does.
+    /// The new size is reported with `WindowEvent::Resized`, and is the inner size of the window.
+    ///
+    /// With automatic sizing, the drawing buffer is resized to match the displayed size again
+    /// when it changes, see `set_auto_size`.
+    fn set_buffer_size(&self, size: PhysicalSize<u32>);
+
+    /// Returns the element drawing the decorations of this window, which contains a title bar
+    /// and the canvas, or `None` if the window is undecorated.
+    ///
+    /// Decorations are drawn by an element that takes the place of the canvas in the document,
+    /// so they are shown by appending this element instead of the canvas. Dragging the title bar
+    /// moves the element away from where it is laid out. The element and the title bar can be
+    /// styled through their `winit-frame` and `winit-title-bar` classes.
+    fn frame(&self) -> Option<HtmlElement>;
+
+    /// Returns whether the window is decorated, see `Window::set_decorations`.
+    fn is_decorated(&self) -> bool;
+
+    /// Returns whether the window is maximized, see `Window::set_maximized`.
+    ///
+    /// A maximized window is pinned to the top left corner of the viewport, and the canvas is
+    /// sized through CSS so that the window fills the viewport. The new size is reported with
+    /// `WindowEvent::Resized` once the canvas has been laid out.
+    fn is_maximized(&self) -> bool;
+
+    /// Shows the given image as the cursor while it is over the canvas, until it is reset with
+    /// `None` or a cursor icon is set with `Window::set_cursor_icon`.
+    ///
+    /// The image is drawn at one image pixel per CSS pixel. Browsers don't show images larger than
+    /// 128 by 128 pixels, for which the default cursor is shown instead.
+    fn set_custom_cursor(&self, cursor: Option<CustomCursor>);
+
+    /// Returns whether the window is minimized, see `Window::set_minimized`.
+    ///
+    /// A minimized window hides its canvas like `Window::set_visible(false)`, which is reported with
+    /// `WindowEvent::Occluded`, until it is restored.
+    fn is_minimized(&self) -> bool;
 }
 
-#[cfg(feature = "stdweb")]
-pub trait WindowBuilderExtStdweb {
-    fn with_canvas(self, canvas: Option<CanvasElement>) -> Self;
+/// A cursor showing an image, see `WindowExtWebSys::set_custom_cursor`.
+#[cfg(feature = "web-sys")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomCursor {
+    pub(crate) image: Icon,
+    pub(crate) hotspot_x: u32,
+    pub(crate) hotspot_y: u32,
 }
 
-#[cfg(feature = "stdweb")]
-impl WindowBuilderExtStdweb for WindowBuilder {
-    fn with_canvas(mut self, canvas: Option<CanvasElement>) -> Self {
-        self.platform_specific.canvas = canvas;
+#[cfg(feature = "web-sys")]
+impl CustomCursor {
+    /// Creates a cursor from the given image. The hotspot is the point of the image that the
+    /// cursor points at, in pixels from its top left corner.
+    pub fn new(image: Icon, hotspot_x: u32, hotspot_y: u32) -> Self {
+        CustomCursor {
+            image,
+            hotspot_x,
+            hotspot_y,
+        }
+    }
+}
+
+/// The orientations the screen can be locked to.
+#[cfg(feature = "web-sys")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OrientationLock {
+    /// Any orientation, which unlike unlocking it disables automatic rotation by the browser.
+    Any,
+    /// The natural orientation of the device.
+    Natural,
+    /// Either landscape orientation.
+    Landscape,
+    /// Either portrait orientation.
+    Portrait,
+    /// Exactly the given orientation.
+    Exact(Orientation),
+}
+
+/// The visibility of the page containing the canvas.
+#[cfg(feature = "web-sys")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VisibilityState {
+    /// The page is at least partially visible.
+    Visible,
+    /// The page is not visible, e.g. because its tab is in the background or the browser is
+    /// minimized.
+    Hidden,
+    /// The page is being prerendered and is not visible to the user.
+    Prerender,
+}
+
+/// Access to the system clipboard.
+///
+/// Browsers only grant access to the clipboard while a user gesture is being handled, so the
+/// methods of this type must be called from within the event loop callback while it is handling
+/// an input event such as `WindowEvent::MouseInput` or `WindowEvent::KeyboardInput`. Outside of
+/// such an event, they fail with `ExternalError::NotSupported`.
+#[cfg(feature = "web-sys")]
+#[derive(Clone)]
+pub struct Clipboard {
+    pub(crate) inner: platform_impl::Clipboard,
+}
+
+#[cfg(feature = "web-sys")]
+impl Clipboard {
+    /// Requests the text content of the clipboard.
+    ///
+    /// The text is read asynchronously and delivered to the window that created this handle as a
+    /// `WindowEvent::ReceivedClipboardText` event, or `WindowEvent::ClipboardReadFailed` if the
+    /// browser refused to read the clipboard, e.g. because the user denied the permission.
+    pub fn read_text(&self) -> Result<(), ExternalError> {
+        self.inner.read_text()
+    }
+
+    /// Replaces the content of the clipboard with the given text.
+    pub fn write_text(&self, text: &str) -> Result<(), ExternalError> {
+        self.inner.write_text(text)
+    }
+}
+
+/// Additional methods on `EventLoop` that are specific to the web.
+#[cfg(feature = "web-sys")]
+pub trait EventLoopExtWebSys {
+    /// A type provided by the user that can be passed through `Event::UserEvent`.
+    type UserEvent;
+
+    /// Initializes the `winit` event loop, like `run`, and calls `on_exit` once it has exited.
+    ///
+    /// When `control_flow` is set to `ControlFlow::Exit`, `Event::LoopDestroyed` is emitted, then
+    /// the listeners of every canvas are removed, pending animation frames are cancelled and
+    /// `event_handler` is dropped, before `on_exit` is called. A new `EventLoop` can then be
+    /// created to start over.
+    fn run_with_exit<F, G>(self, event_handler: F, on_exit: G)
+    where
+        F: 'static
+            + FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow),
+        G: 'static + FnOnce();
+
+    /// Dispatches the events received since the last call to `event_handler`, then emits
+    /// `RedrawRequested` for the windows that requested a redraw, and returns, for embedding the
+    /// event loop in one driven by the application, e.g. from its own `requestAnimationFrame`
+    /// callback.
+    ///
+    /// Unlike `run`, this takes over no scheduling: events are held until the next call, and
+    /// windows are only redrawn then. Events coalesced per animation frame, like `Resized`, are
+    /// dispatched by the next call as well. Each call emits `Event::NewEvents`, with
+    /// `StartCause::Init` the first time and `StartCause::Poll` afterwards, followed by
+    /// `MainEventsCleared` and `RedrawEventsCleared` like an iteration of `run`. The returned
+    /// `ControlFlow` is the one set by `event_handler`, which is not applied otherwise, e.g. to
+    /// stop pumping on `ControlFlow::Exit`.
+    fn pump_events<F>(&mut self, event_handler: F) -> ControlFlow
+    where
+        F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow);
+}
+
+#[cfg(feature = "web-sys")]
+impl<T> EventLoopExtWebSys for EventLoop<T> {
+    type UserEvent = T;
+
+    fn run_with_exit<F, G>(self, event_handler: F, on_exit: G)
+    where
+        F: 'static
+            + FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow),
+        G: 'static + FnOnce(),
+    {
+        self.event_loop.run_with_exit(event_handler, on_exit)
+    }
+
+    fn pump_events<F>(&mut self, event_handler: F) -> ControlFlow
+    where
+        F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow),
+    {
+        self.event_loop.pump_events(event_handler)
+    }
+}
+
+/// Options of `EventLoop` that are specific to the web, for every window created with it.
+///
+/// Each option is a default that the `WindowBuilderExtWebSys` method of the same name overrides
+/// for a single window. Windows that were already built keep their settings.
+#[cfg(feature = "web-sys")]
+pub trait EventLoopBuilderExtWebSys {
+    /// Appends the canvas of each window to the given node, like
+    /// `WindowBuilderExtWebSys::with_container`. Windows built with their own canvas or container
+    /// are left where they are.
+    fn with_container(self, container: Option<Node>) -> Self;
+
+    /// Registers the `wheel` and touch listeners of every canvas as passive, like
+    /// `WindowBuilderExtWebSys::with_passive_listeners`.
+    ///
+    /// Disabled by default.
+    fn with_passive_listeners(self, passive: bool) -> Self;
+
+    /// Prevents the browser from handling any keyboard, wheel and touch input of every canvas, like
+    /// `WindowBuilderExtWebSys::with_prevent_default`.
+    ///
+    /// Disabled by default.
+    fn with_prevent_default(self, prevent_default: bool) -> Self;
+}
+
+#[cfg(feature = "web-sys")]
+impl<T> EventLoopBuilderExtWebSys for EventLoop<T> {
+    fn with_container(self, container: Option<Node>) -> Self {
+        self.event_loop.set_container(container);
+
+        self
+    }
+
+    fn with_passive_listeners(self, passive: bool) -> Self {
+        self.event_loop.set_passive_listeners(passive);
+
+        self
+    }
+
+    fn with_prevent_default(self, prevent_default: bool) -> Self {
+        self.event_loop.set_prevent_default(prevent_default);
 
         self
     }
 }
 
+/// Additional methods on `EventLoopWindowTarget` that are specific to the web.
+#[cfg(feature = "web-sys")]
+pub trait EventLoopWindowTargetExtWebSys {
+    /// A type provided by the user that can be passed through `Event::UserEvent`.
+    type UserEvent;
+
+    /// Returns the timestamp the browser passed to the latest animation frame callback, or `None`
+    /// before the first frame.
+    ///
+    /// The timestamp is a `DOMHighResTimeStamp` in milliseconds, in the same clock domain as
+    /// `performance.now()`, which also drives `Instant` and thus `ControlFlow::WaitUntil`. While
+    /// handling `Event::RedrawRequested`, it is the start time of the frame being drawn. It never
+    /// decreases.
+    fn frame_timestamp(&self) -> Option<f64>;
+
+    /// Returns the `key` of the browser's keyboard event behind the `WindowEvent::KeyboardInput`
+    /// handled last, e.g. `"a"`, `"A"` or `"ArrowLeft"`, which depends on the keyboard layout and
+    /// the modifiers, unlike the scancode. While handling a `KeyboardInput` event, and the
+    /// `DeviceEvent::Key` following it, this is the key of that event.
+    ///
+    /// Returns `None` for the synthetic key releases emitted when the canvas loses focus, and
+    /// before the first `KeyboardInput` event.
+    fn current_key(&self) -> Option<String>;
+
+    /// Limits how many animation frames are run per second, or removes the limit with `None`.
+    ///
+    /// Animation frames emit `Event::RedrawRequested` and `Event::RedrawEventsCleared`, so this
+    /// caps the rate at which the windows are redrawn. Frames are delayed until the interval since
+    /// the previous one has passed, and then run on the next refresh of the display, so the actual
+    /// rate is rounded down to a fraction of the refresh rate.
+    ///
+    /// A maximum frame rate that isn't positive and finite, e.g. `0.0` or `f64::NAN`, removes the
+    /// limit like `None`.
+    fn set_max_frame_rate(&self, max_frame_rate: Option<f64>);
+
+    /// Sends an event through the event loop as if the browser had caused it, e.g. to script a
+    /// sequence of events in tests.
+    ///
+    /// The event is dispatched right away while the event loop waits for events, and queued
+    /// otherwise, e.g. to be dispatched after the event being handled. Before the event loop
+    /// runs, or when it is driven with `EventLoopExtWebSys::pump_events`, events are queued until
+    /// the next call to `pump_events`, which dispatches them in order and returns the resulting
+    /// `ControlFlow`. Since `EventLoop` dereferences to `EventLoopWindowTarget`, events can be
+    /// pushed before it runs. A pushed `WindowEvent::KeyboardInput` has no `current_key`.
+    ///
+    /// This is only available with the `synthetic-events` feature.
+    #[cfg(feature = "synthetic-events")]
+    fn push_event(&self, event: Event<Self::UserEvent>);
+
+    /// Emits the messages the given Web Worker posts to the page as `Event::UserEvent`, which
+    /// makes up for `EventLoopProxy` not being `Send`.
+    ///
+    /// The worker sends its messages with `postMessage`, e.g. through
+    /// `DedicatedWorkerGlobalScope::post_message`. `deserialize` turns the data of each message
+    /// into a user event; messages for which it returns `None` are ignored. The worker is listened
+    /// to until the event loop exits.
+    fn listen_to_worker<F>(&self, worker: &Worker, deserialize: F)
+    where
+        F: 'static + FnMut(JsValue) -> Option<Self::UserEvent>;
+}
+
+#[cfg(feature = "web-sys")]
+impl<T> EventLoopWindowTargetExtWebSys for EventLoopWindowTarget<T> {
+    type UserEvent = T;
+
+    fn frame_timestamp(&self) -> Option<f64> {
+        self.p.frame_timestamp()
+    }
+
+    fn current_key(&self) -> Option<String> {
+        self.p.current_key()
+    }
+
+    fn set_max_frame_rate(&self, max_frame_rate: Option<f64>) {
+        self.p.set_max_frame_rate(max_frame_rate)
+    }
+
+    #[cfg(feature = "synthetic-events")]
+    fn push_event(&self, event: Event<Self::UserEvent>) {
+        self.p.push_event(event)
+    }
+
+    fn listen_to_worker<F>(&self, worker: &Worker, deserialize: F)
+    where
+        F: 'static + FnMut(JsValue) -> Option<T>,
+    {
+        self.p.proxy().listen_to_worker(worker, deserialize)
+    }
+}
+
 #[cfg(feature = "web-sys")]
 pub trait WindowBuilderExtWebSys {
+    /// Builds the window on an existing canvas instead of creating a new one.
+    ///
+    /// The canvas stays where it is in the document and keeps its size, unless an inner size is
+    /// set on the builder. A `tabindex` is added if it has none, so that it can receive keyboard
+    /// events. Dropping the window only removes its event listeners, leaving the canvas in the
+    /// document.
     fn with_canvas(self, canvas: Option<HtmlCanvasElement>) -> Self;
+
+    /// Appends the canvas to the given node when the window is built, e.g. an element or the
+    /// `ShadowRoot` of a web component. If the window is decorated, the element drawing the
+    /// decorations takes the place of the canvas there.
+    ///
+    /// Canvases in a shadow root are supported, so their focus, fullscreen and pointer lock are
+    /// tracked within it. By default, the canvas is not added to the document, unless a container
+    /// is set on the event loop with `EventLoopBuilderExtWebSys::with_container`.
+    fn with_container(self, container: Option<Node>) -> Self;
+
+    /// Whether `WindowEvent::KeyboardInput` is emitted for presses generated by the key repeat of
+    /// the system while a key is held down. When disabled, every `ElementState::Pressed` event is
+    /// a fresh press. `WindowEvent::ReceivedCharacter` keeps repeating either way, so text input
+    /// is unaffected.
+    ///
+    /// Enabled by default.
+    fn with_key_repeat(self, key_repeat: bool) -> Self;
+
+    /// Prevents the browser from handling the given key combination while the canvas is focused,
+    /// e.g. to keep `Ctrl+S` from opening the save dialog. The `KeyboardInput` event is still
+    /// emitted, but `ReceivedCharacter` is not, as the browser produces no text for the key press.
+    ///
+    /// The modifiers have to match exactly. Can be called multiple times to register several
+    /// combinations; all other keys are left to the browser.
+    fn with_prevent_default_key(self, key: VirtualKeyCode, modifiers: ModifiersState) -> Self;
+
+    /// How `WindowEvent::CursorMoved` events are coalesced. See [`CursorMoveCoalescing`].
+    ///
+    /// Defaults to `CursorMoveCoalescing::Browser`.
+    fn with_cursor_move_coalescing(self, coalescing: CursorMoveCoalescing) -> Self;
+
+    /// Registers the `wheel` and touch listeners of the canvas as passive, so that the browser
+    /// never waits for them before scrolling. As passive listeners can't prevent the default
+    /// action, the page then scrolls with the wheel even while the canvas is focused.
+    ///
+    /// Disabled by default, unless enabled on the event loop with `EventLoopBuilderExtWebSys`. The
+    /// `pointermove` listener is passive either way.
+    fn with_passive_listeners(self, passive: bool) -> Self;
+
+    /// Prevents the browser from handling any keyboard, wheel and touch input of the canvas, and
+    /// from opening the context menu over it, e.g. to keep the page from scrolling or zooming.
+    /// Like with `with_prevent_default_key`, no `ReceivedCharacter` events are emitted then, as the
+    /// browser produces no text.
+    ///
+    /// Disabled by default, unless enabled on the event loop with `EventLoopBuilderExtWebSys`.
+    /// Passive listeners can't prevent the default action, so wheel and touch input is not
+    /// affected if `with_passive_listeners` is enabled.
+    fn with_prevent_default(self, prevent_default: bool) -> Self;
+
+    /// Prevents the browser from opening its context menu over the canvas, so that the secondary
+    /// button can be used by the application. `WindowEvent::MouseInput` is emitted for
+    /// `MouseButton::Right` either way.
+    ///
+    /// Disabled by default, unless `with_prevent_default` is enabled.
+    fn with_prevent_context_menu(self, prevent_context_menu: bool) -> Self;
+
+    /// Sets the `tabindex` of the canvas. The canvas needs one to be focused, e.g. by clicking on
+    /// it, and thus to receive keyboard input.
+    ///
+    /// Defaults to `0`, which puts the canvas in the order of the document, unless the canvas given
+    /// to `with_canvas` already has a `tabindex`.
+    fn with_tab_index(self, tab_index: i32) -> Self;
+
+    /// Focuses the canvas right after the window is built, once the code building it has run, so
+    /// that a new canvas can be added to the document first.
+    ///
+    /// Disabled by default.
+    fn with_focus(self, focus: bool) -> Self;
+
+    /// Whether the drawing buffer of the canvas is resized to match the size it is displayed at,
+    /// when entering or exiting fullscreen and when the scale factor changes, see
+    /// `WindowExtWebSys::set_auto_size`.
+    ///
+    /// Enabled by default. When disabled, a canvas that isn't given an inner size keeps the size
+    /// it was created with.
+    fn with_auto_size(self, auto_size: bool) -> Self;
+
+    /// Captures the pointer when a button is pressed over the canvas, until all buttons are
+    /// released, so that `CursorMoved` and `MouseInput` keep being emitted while the pointer is
+    /// dragged outside of it. `CursorLeft` is only emitted once the capture ended.
+    ///
+    /// Disabled by default. Touches are always captured by the browser.
+    fn with_pointer_capture(self, pointer_capture: bool) -> Self;
+
+    /// Hides the canvas with `visibility: hidden` until the first `Event::RedrawRequested` of the
+    /// window has been handled, or until `WindowExtWebSys::ready` is called, so that the page
+    /// doesn't show a blank canvas before the first frame is drawn. The canvas keeps its place in
+    /// the layout meanwhile.
+    ///
+    /// Disabled by default.
+    fn with_hidden_until_ready(self, hidden_until_ready: bool) -> Self;
+
+    /// Sets `touch-action: none` on the canvas, so that touches over it neither scroll nor zoom
+    /// the page, and are only handled by the application, even when the listeners are passive.
+    ///
+    /// Disabled by default, in which case it is up to the touch listeners to prevent the default
+    /// action, which browsers may not wait for.
+    fn with_prevent_touch_action(self, prevent_touch_action: bool) -> Self;
 }
 
 #[cfg(feature = "web-sys")]
@@ -50,4 +593,160 @@ impl WindowBuilderExtWebSys for WindowBuilder {
 
         self
     }
+
+    fn with_container(mut self, container: Option<Node>) -> Self {
+        self.platform_specific.container = container;
+
+        self
+    }
+
+    fn with_key_repeat(mut self, key_repeat: bool) -> Self {
+        self.platform_specific.ignore_key_repeat = !key_repeat;
+
+        self
+    }
+
+    fn with_prevent_default_key(mut self, key: VirtualKeyCode, modifiers: ModifiersState) -> Self {
+        self.platform_specific.prevented_keys.push((key, modifiers));
+
+        self
+    }
+
+    fn with_cursor_move_coalescing(mut self, coalescing: CursorMoveCoalescing) -> Self {
+        self.platform_specific.cursor_move_coalescing = coalescing;
+
+        self
+    }
+
+    fn with_passive_listeners(mut self, passive: bool) -> Self {
+        self.platform_specific.passive_listeners = Some(passive);
+
+        self
+    }
+
+    fn with_prevent_default(mut self, prevent_default: bool) -> Self {
+        self.platform_specific.prevent_default = Some(prevent_default);
+
+        self
+    }
+
+    fn with_prevent_context_menu(mut self, prevent_context_menu: bool) -> Self {
+        self.platform_specific.prevent_context_menu = prevent_context_menu;
+
+        self
+    }
+
+    fn with_tab_index(mut self, tab_index: i32) -> Self {
+        self.platform_specific.tab_index = Some(tab_index);
+
+        self
+    }
+
+    fn with_focus(mut self, focus: bool) -> Self {
+        self.platform_specific.focus = focus;
+
+        self
+    }
+
+    fn with_auto_size(mut self, auto_size: bool) -> Self {
+        self.platform_specific.manual_size = !auto_size;
+
+        self
+    }
+
+    fn with_pointer_capture(mut self, pointer_capture: bool) -> Self {
+        self.platform_specific.pointer_capture = pointer_capture;
+
+        self
+    }
+
+    fn with_hidden_until_ready(mut self, hidden_until_ready: bool) -> Self {
+        self.platform_specific.hidden_until_ready = hidden_until_ready;
+
+        self
+    }
+
+    fn with_prevent_touch_action(mut self, prevent_touch_action: bool) -> Self {
+        self.platform_specific.prevent_touch_action = prevent_touch_action;
+
+        self
+    }
+}
+
+/// How pointer moves over the canvas are turned into `WindowEvent::CursorMoved` events.
+#[cfg(feature = "web-sys")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CursorMoveCoalescing {
+    /// A `CursorMoved` event is sent for every `pointermove` event of the browser, which may
+    /// already combine several moves into one.
+    Browser,
+    /// Only the most recent position of each pointer is sent, once per animation frame and right
+    /// before `Event::RedrawRequested`. Moves are still sent before any other pointer event of the
+    /// window, so that the latter happen at the right position.
+    Frame,
+    /// Every position combined into a `pointermove` event is sent, as returned by
+    /// `getCoalescedEvents()`, for applications that need the full path of the pointer, such as
+    /// drawing tools. The `WindowEvent::Touch` events of a pen in contact with the screen follow
+    /// the full path as well. Falls back to `Browser` if `getCoalescedEvents()` isn't supported.
+    None,
+}
+
+#[cfg(feature = "web-sys")]
+impl Default for CursorMoveCoalescing {
+    fn default() -> Self {
+        CursorMoveCoalescing::Browser
+    }
+}
+
+/// The kind of device behind a pointer, from `PointerEvent.pointerType`.
+#[cfg(feature = "web-sys")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PointerType {
+    /// A mouse, or any pointer the browser doesn't report as a pen or touch.
+    Mouse,
+    /// A pen or stylus.
+    Pen,
+    /// A touch on a touch screen.
+    Touch,
+}
+
+/// Additional methods on `DeviceId` that are specific to the web.
+#[cfg(feature = "web-sys")]
+pub trait DeviceIdExtWebSys {
+    /// Returns the kind of pointer the device is, or `None` if it isn't a pointer, e.g. for
+    /// keyboards and gamepads.
+    fn pointer_type(&self) -> Option<PointerType>;
+}
+
+#[cfg(feature = "web-sys")]
+impl DeviceIdExtWebSys for DeviceId {
+    fn pointer_type(&self) -> Option<PointerType> {
+        self.0.pointer_type()
+    }
+}
+
+/// Additional methods on `WindowId` that are specific to the web.
+#[cfg(feature = "web-sys")]
+pub trait WindowIdExtWebSys: Sized {
+    /// Returns the id of the window the given canvas belongs to, or `None` if it isn't the canvas
+    /// of a window. The window may have been dropped since.
+    ///
+    /// The id is read from the `data-raw-handle` attribute of the canvas, see `raw_handle`.
+    fn from_canvas(canvas: &HtmlCanvasElement) -> Option<Self>;
+
+    /// Returns the value of the `data-raw-handle` attribute of the window's canvas, which is also
+    /// the id of its `RawWindowHandle`. JavaScript code can find the canvas of a window by it,
+    /// e.g. with the `canvas[data-raw-handle="1"]` selector.
+    fn raw_handle(&self) -> u32;
+}
+
+#[cfg(feature = "web-sys")]
+impl WindowIdExtWebSys for WindowId {
+    fn from_canvas(canvas: &HtmlCanvasElement) -> Option<Self> {
+        platform_impl::WindowId::from_canvas(canvas).map(WindowId)
+    }
+
+    fn raw_handle(&self) -> u32 {
+        self.0.raw_handle()
+    }
 }
@@ -1,4 +1,4 @@
-#![cfg(target_os = "windows")]
+#![cfg(all(target_os = "windows", not(feature = "headless")))]
 
 use std::os::raw::c_void;
 
@@ -15,11 +15,13 @@ use std::{
 use crate::{
     error::{ExternalError, NotSupportedError},
     events::{Touch, TouchPhase},
-    window::MonitorHandle as RootMonitorHandle,
+    window::{CursorGrabMode, MonitorHandle as RootMonitorHandle},
     CreationError, CursorIcon, Event, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize,
     WindowAttributes, WindowEvent, WindowId as RootWindowId,
 };
-use raw_window_handle::{android::AndroidHandle, RawWindowHandle};
+use raw_window_handle::{
+    AndroidDisplayHandle, AndroidNdkWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
 use CreationError::OsError;
 
 pub type OsError = std::io::Error;
@@ -77,6 +79,7 @@ impl EventLoop {
                             },
                             location,
                             force: None, // TODO
+                            radius: None,
                             id: motion.pointer_id as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -346,7 +349,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, _grab: bool) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, _mode: CursorGrabMode) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
@@ -407,6 +410,17 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn focus_window(&self) {
+        // Not implemented yet
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        // Not implemented yet
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn current_monitor(&self) -> RootMonitorHandle {
         RootMonitorHandle {
@@ -433,11 +447,13 @@ impl Window {
 
     #[inline]
     pub fn raw_window_handle(&self) -> RawWindowHandle {
-        let handle = AndroidHandle {
-            a_native_window: self.native_window,
-            ..WindowsHandle::empty()
-        };
-        RawWindowHandle::Android(handle)
+        let mut handle = AndroidNdkWindowHandle::empty();
+        handle.a_native_window = self.native_window;
+        RawWindowHandle::AndroidNdk(handle)
+    }
+
+    pub fn raw_display_handle(&self) -> RawDisplayHandle {
+        RawDisplayHandle::Android(AndroidDisplayHandle::empty())
     }
 }
 
@@ -0,0 +1,222 @@
+use super::{monitor, window};
+use crate::event::{Event, StartCause};
+use crate::event_loop::{self as root, ControlFlow, EventLoopClosed};
+use crate::window::WindowId;
+
+use instant::Instant;
+use std::cell::{Cell, RefCell};
+use std::collections::{vec_deque::IntoIter as VecDequeIter, VecDeque};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+
+pub struct EventLoop<T: 'static> {
+    elw: root::EventLoopWindowTarget<T>,
+}
+
+impl<T> EventLoop<T> {
+    pub fn new() -> Self {
+        EventLoop {
+            elw: root::EventLoopWindowTarget {
+                p: WindowTarget::new(),
+                _marker: PhantomData,
+            },
+        }
+    }
+
+    pub fn available_monitors(&self) -> VecDequeIter<monitor::Handle> {
+        let mut monitors = VecDeque::new();
+        monitors.push_back(monitor::Handle);
+        monitors.into_iter()
+    }
+
+    pub fn primary_monitor(&self) -> monitor::Handle {
+        monitor::Handle
+    }
+
+    // Unlike on other platforms, `run` returns once the event loop has exited, so that tests can
+    // check the state of the application afterwards
+    pub fn run<F>(mut self, event_handler: F)
+    where
+        F: 'static + FnMut(Event<T>, &root::EventLoopWindowTarget<T>, &mut ControlFlow),
+    {
+        self.run_return(event_handler)
+    }
+
+    pub fn run_return<F>(&mut self, mut event_handler: F)
+    where
+        F: FnMut(Event<T>, &root::EventLoopWindowTarget<T>, &mut ControlFlow),
+    {
+        let mut control_flow = ControlFlow::default();
+        let mut handle =
+            |event, control_flow: &mut ControlFlow| event_handler(event, &self.elw, control_flow);
+
+        let mut start_cause = StartCause::Init;
+        loop {
+            handle(Event::NewEvents(start_cause), &mut control_flow);
+
+            // Events pushed while they are handled are dispatched within the same iteration
+            while let Some(event) = self.elw.p.next_event() {
+                handle(event, &mut control_flow);
+            }
+            handle(Event::MainEventsCleared, &mut control_flow);
+
+            for id in self.elw.p.take_redraw_requests() {
+                handle(Event::RedrawRequested(WindowId(id)), &mut control_flow);
+            }
+            handle(Event::RedrawEventsCleared, &mut control_flow);
+
+            start_cause = match control_flow {
+                ControlFlow::Poll => StartCause::Poll,
+                ControlFlow::Wait => {
+                    let start = Instant::now();
+                    self.elw.p.wait(None);
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: None,
+                    }
+                }
+                ControlFlow::WaitUntil(requested_resume) => {
+                    let start = Instant::now();
+                    if self.elw.p.wait(Some(requested_resume)) {
+                        StartCause::WaitCancelled {
+                            start,
+                            requested_resume: Some(requested_resume),
+                        }
+                    } else {
+                        StartCause::ResumeTimeReached {
+                            start,
+                            requested_resume,
+                        }
+                    }
+                }
+                ControlFlow::Exit => break,
+            };
+        }
+
+        handle(Event::LoopDestroyed, &mut control_flow);
+    }
+
+    pub fn create_proxy(&self) -> Proxy<T> {
+        self.elw.p.proxy()
+    }
+
+    pub fn window_target(&self) -> &root::EventLoopWindowTarget<T> {
+        &self.elw
+    }
+}
+
+pub struct WindowTarget<T: 'static> {
+    events: Rc<RefCell<VecDeque<Event<T>>>>,
+    redraw_requests: Arc<Mutex<Vec<window::Id>>>,
+    id: Rc<Cell<u32>>,
+    user_events: Rc<Receiver<T>>,
+    user_event_sender: Sender<T>,
+}
+
+impl<T> Clone for WindowTarget<T> {
+    fn clone(&self) -> Self {
+        WindowTarget {
+            events: self.events.clone(),
+            redraw_requests: self.redraw_requests.clone(),
+            id: self.id.clone(),
+            user_events: self.user_events.clone(),
+            user_event_sender: self.user_event_sender.clone(),
+        }
+    }
+}
+
+impl<T> WindowTarget<T> {
+    pub fn new() -> Self {
+        let (user_event_sender, user_events) = mpsc::channel();
+
+        WindowTarget {
+            events: Rc::new(RefCell::new(VecDeque::new())),
+            redraw_requests: Arc::new(Mutex::new(Vec::new())),
+            id: Rc::new(Cell::new(0)),
+            user_events: Rc::new(user_events),
+            user_event_sender,
+        }
+    }
+
+    pub fn proxy(&self) -> Proxy<T> {
+        Proxy {
+            sender: self.user_event_sender.clone(),
+        }
+    }
+
+    pub fn push_event(&self, event: Event<T>) {
+        self.events.borrow_mut().push_back(event);
+    }
+
+    // Generate a strictly increasing ID
+    // This is used to differentiate windows when handling events
+    pub fn generate_id(&self) -> window::Id {
+        let id = self.id.get() + 1;
+        self.id.set(id);
+
+        window::Id(id)
+    }
+
+    pub fn redraw_requests(&self) -> Arc<Mutex<Vec<window::Id>>> {
+        self.redraw_requests.clone()
+    }
+
+    // User events sent through a proxy are queued behind the events pushed so far
+    fn next_event(&self) -> Option<Event<T>> {
+        while let Ok(event) = self.user_events.try_recv() {
+            self.push_event(Event::UserEvent(event));
+        }
+        self.events.borrow_mut().pop_front()
+    }
+
+    // Redraws are requested at most once per window and iteration, in the order they were requested
+    fn take_redraw_requests(&self) -> Vec<window::Id> {
+        std::mem::take(&mut *self.redraw_requests.lock().unwrap())
+    }
+
+    // Only events sent through a proxy can arrive while waiting, as nothing else runs in the
+    // meantime. Returns whether the wait was cancelled by an event rather than a timeout.
+    fn wait(&self, until: Option<Instant>) -> bool {
+        if !self.events.borrow().is_empty() || !self.redraw_requests.lock().unwrap().is_empty() {
+            return true;
+        }
+
+        let event = match until {
+            Some(until) => {
+                let timeout = until.saturating_duration_since(Instant::now());
+                match self.user_events.recv_timeout(timeout) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => return false,
+                    Err(RecvTimeoutError::Disconnected) => unreachable!(),
+                }
+            }
+            // The window target holds a sender itself, so this never fails
+            None => self.user_events.recv().unwrap(),
+        };
+        self.push_event(Event::UserEvent(event));
+
+        true
+    }
+}
+
+pub struct Proxy<T: 'static> {
+    sender: Sender<T>,
+}
+
+impl<T: 'static> Proxy<T> {
+    pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        self.sender
+            .send(event)
+            .map_err(|mpsc::SendError(event)| EventLoopClosed(event))
+    }
+}
+
+impl<T: 'static> Clone for Proxy<T> {
+    fn clone(&self) -> Self {
+        Proxy {
+            sender: self.sender.clone(),
+        }
+    }
+}
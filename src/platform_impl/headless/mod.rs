@@ -0,0 +1,27 @@
+// Brief introduction to the internals of the headless backend:
+// The headless backend has no windowing system to talk to. Windows only hold the state set through
+// their methods, and the event loop dispatches the events pushed into its queue, through
+// `EventLoopWindowTargetExtHeadless::push_event` or an `EventLoopProxy`, in the order they were
+// pushed. This makes it possible to run winit applications in tests and on CI machines without a
+// display.
+//
+// 'device', 'error', 'monitor', and 'window' define the headless versions of winit's
+// cross-platform structures. The 'event_loop' module holds the event queue that is shared between
+// the event loop, its window target and its windows, and drives the user's event handler.
+
+mod device;
+mod error;
+mod event_loop;
+mod monitor;
+mod window;
+
+pub use self::device::Id as DeviceId;
+pub use self::error::OsError;
+pub use self::event_loop::{
+    EventLoop, Proxy as EventLoopProxy, WindowTarget as EventLoopWindowTarget,
+};
+pub use self::monitor::{Handle as MonitorHandle, Mode as VideoMode};
+pub use self::window::{
+    Id as WindowId, PlatformSpecificBuilderAttributes as PlatformSpecificWindowBuilderAttributes,
+    Window,
+};
@@ -0,0 +1,54 @@
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::monitor::{MonitorHandle, VideoMode};
+
+// There is a single virtual monitor, with a fixed size
+const SIZE: PhysicalSize<u32> = PhysicalSize {
+    width: 1920,
+    height: 1080,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle;
+
+impl Handle {
+    pub fn scale_factor(&self) -> f64 {
+        1.0
+    }
+
+    pub fn position(&self) -> PhysicalPosition<i32> {
+        PhysicalPosition { x: 0, y: 0 }
+    }
+
+    pub fn name(&self) -> Option<String> {
+        Some("headless".to_owned())
+    }
+
+    pub fn size(&self) -> PhysicalSize<u32> {
+        SIZE
+    }
+
+    pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
+        std::iter::once(VideoMode { video_mode: Mode })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Mode;
+
+impl Mode {
+    pub fn size(&self) -> PhysicalSize<u32> {
+        SIZE
+    }
+
+    pub fn bit_depth(&self) -> u16 {
+        32
+    }
+
+    pub fn refresh_rate(&self) -> u16 {
+        60
+    }
+
+    pub fn monitor(&self) -> MonitorHandle {
+        MonitorHandle { inner: Handle }
+    }
+}
@@ -0,0 +1,326 @@
+use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
+use crate::icon::Icon;
+use crate::monitor::MonitorHandle as RootMH;
+use crate::window::{CursorGrabMode, CursorIcon, Fullscreen, WindowAttributes};
+
+use super::{monitor, EventLoopWindowTarget};
+
+use std::collections::vec_deque::IntoIter as VecDequeIter;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+// Windows are `Send` and `Sync` on every other platform, so their state is behind a mutex
+pub struct Window {
+    id: Id,
+    state: Mutex<State>,
+    redraw_requests: Arc<Mutex<Vec<Id>>>,
+}
+
+struct State {
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    min_size: Option<Size>,
+    max_size: Option<Size>,
+    fullscreen: Option<Fullscreen>,
+}
+
+impl Window {
+    pub fn new<T>(
+        target: &EventLoopWindowTarget<T>,
+        attr: WindowAttributes,
+        _: PlatformSpecificBuilderAttributes,
+    ) -> Result<Self, RootOE> {
+        let window = Window {
+            id: target.generate_id(),
+            state: Mutex::new(State {
+                position: PhysicalPosition { x: 0, y: 0 },
+                size: PhysicalSize {
+                    width: 0,
+                    height: 0,
+                },
+                min_size: attr.min_inner_size,
+                max_size: attr.max_inner_size,
+                fullscreen: attr.fullscreen,
+            }),
+            redraw_requests: target.redraw_requests(),
+        };
+
+        window.set_inner_size(attr.inner_size.unwrap_or(Size::Logical(LogicalSize {
+            width: 1024.0,
+            height: 768.0,
+        })));
+
+        Ok(window)
+    }
+
+    pub fn set_title(&self, _title: &str) {
+        // Intentionally a no-op, there is no title bar
+    }
+
+    pub fn set_visible(&self, _visible: bool) {
+        // Intentionally a no-op, nothing is displayed
+    }
+
+    pub fn is_visible(&self) -> Option<bool> {
+        None
+    }
+
+    pub fn request_redraw(&self) {
+        let mut redraw_requests = self.redraw_requests.lock().unwrap();
+        if !redraw_requests.contains(&self.id) {
+            redraw_requests.push(self.id);
+        }
+    }
+
+    pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        Ok(self.state.lock().unwrap().position)
+    }
+
+    pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        // Note: there are no window decorations, so this is equal to `outer_position`.
+        self.outer_position()
+    }
+
+    pub fn set_outer_position(&self, position: Position) {
+        self.state.lock().unwrap().position = position.to_physical(self.scale_factor());
+    }
+
+    #[inline]
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        self.state.lock().unwrap().size
+    }
+
+    #[inline]
+    pub fn outer_size(&self) -> PhysicalSize<u32> {
+        // Note: there are no window decorations, so this is equal to `inner_size`.
+        self.inner_size()
+    }
+
+    #[inline]
+    pub fn set_inner_size(&self, size: Size) {
+        let scale_factor = self.scale_factor();
+        let mut state = self.state.lock().unwrap();
+        let mut size: PhysicalSize<u32> = size.to_physical(scale_factor);
+        if let Some(max_size) = state.max_size {
+            let max_size = max_size.to_physical::<u32>(scale_factor);
+            size.width = size.width.min(max_size.width);
+            size.height = size.height.min(max_size.height);
+        }
+        if let Some(min_size) = state.min_size {
+            let min_size = min_size.to_physical::<u32>(scale_factor);
+            size.width = size.width.max(min_size.width);
+            size.height = size.height.max(min_size.height);
+        }
+        state.size = size;
+    }
+
+    #[inline]
+    pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
+        self.state.lock().unwrap().min_size = dimensions;
+        self.set_inner_size(Size::Physical(self.inner_size()));
+    }
+
+    #[inline]
+    pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
+        self.state.lock().unwrap().max_size = dimensions;
+        self.set_inner_size(Size::Physical(self.inner_size()));
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, _resizable: bool) {
+        // Intentionally a no-op: nobody can resize the window
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.current_monitor().scale_factor()
+    }
+
+    #[inline]
+    pub fn set_cursor_icon(&self, _cursor: CursorIcon) {
+        // Intentionally a no-op, there is no cursor
+    }
+
+    #[inline]
+    pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
+        // Intentionally a no-op, there is no cursor
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_cursor_grab(&self, _mode: CursorGrabMode) -> Result<(), ExternalError> {
+        // Intentionally a no-op, there is no cursor
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_cursor_visible(&self, _visible: bool) {
+        // Intentionally a no-op, there is no cursor
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, _minimized: bool) {
+        // Intentionally a no-op, nothing is displayed
+    }
+
+    #[inline]
+    pub fn set_maximized(&self, _maximized: bool) {
+        // Intentionally a no-op, nothing is displayed
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<Fullscreen> {
+        self.state.lock().unwrap().fullscreen.clone()
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.state.lock().unwrap().fullscreen = fullscreen;
+    }
+
+    #[inline]
+    pub fn set_decorations(&self, _decorations: bool) {
+        // Intentionally a no-op, there are no decorations
+    }
+
+    #[inline]
+    pub fn set_always_on_top(&self, _always_on_top: bool) {
+        // Intentionally a no-op, no window ordering
+    }
+
+    #[inline]
+    pub fn set_window_icon(&self, _window_icon: Option<Icon>) {
+        // Intentionally a no-op, nothing is displayed
+    }
+
+    #[inline]
+    pub fn set_ime_position(&self, _position: Position) {
+        // Intentionally a no-op, there is no input method
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // Intentionally a no-op, there is no input method
+    }
+
+    #[inline]
+    pub fn focus_window(&self) {
+        // Intentionally a no-op, there is no keyboard
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        // There is nothing to drag the window with
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn current_monitor(&self) -> RootMH {
+        RootMH {
+            inner: monitor::Handle,
+        }
+    }
+
+    #[inline]
+    pub fn available_monitors(&self) -> VecDequeIter<monitor::Handle> {
+        let mut monitors = VecDeque::new();
+        monitors.push_back(monitor::Handle);
+        monitors.into_iter()
+    }
+
+    #[inline]
+    pub fn primary_monitor(&self) -> monitor::Handle {
+        monitor::Handle
+    }
+
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    // There is no native window, so the handles of the target platform are returned empty
+    #[inline]
+    pub fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
+        #[cfg(target_os = "windows")]
+        let handle = raw_window_handle::RawWindowHandle::Win32(
+            raw_window_handle::Win32WindowHandle::empty(),
+        );
+        #[cfg(target_os = "macos")]
+        let handle = raw_window_handle::RawWindowHandle::AppKit(
+            raw_window_handle::AppKitWindowHandle::empty(),
+        );
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        let handle =
+            raw_window_handle::RawWindowHandle::Xlib(raw_window_handle::XlibWindowHandle::empty());
+        #[cfg(target_os = "android")]
+        let handle = raw_window_handle::RawWindowHandle::AndroidNdk(
+            raw_window_handle::AndroidNdkWindowHandle::empty(),
+        );
+        #[cfg(target_os = "ios")]
+        let handle = raw_window_handle::RawWindowHandle::UiKit(
+            raw_window_handle::UiKitWindowHandle::empty(),
+        );
+        #[cfg(target_arch = "wasm32")]
+        let handle = {
+            let mut handle = raw_window_handle::WebWindowHandle::empty();
+            handle.id = self.id.0;
+            raw_window_handle::RawWindowHandle::Web(handle)
+        };
+
+        handle
+    }
+
+    #[inline]
+    pub fn raw_display_handle(&self) -> raw_window_handle::RawDisplayHandle {
+        #[cfg(target_os = "windows")]
+        let handle = raw_window_handle::RawDisplayHandle::Windows(
+            raw_window_handle::WindowsDisplayHandle::empty(),
+        );
+        #[cfg(target_os = "macos")]
+        let handle = raw_window_handle::RawDisplayHandle::AppKit(
+            raw_window_handle::AppKitDisplayHandle::empty(),
+        );
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        let handle = raw_window_handle::RawDisplayHandle::Xlib(
+            raw_window_handle::XlibDisplayHandle::empty(),
+        );
+        #[cfg(target_os = "android")]
+        let handle = raw_window_handle::RawDisplayHandle::Android(
+            raw_window_handle::AndroidDisplayHandle::empty(),
+        );
+        #[cfg(target_os = "ios")]
+        let handle = raw_window_handle::RawDisplayHandle::UiKit(
+            raw_window_handle::UiKitDisplayHandle::empty(),
+        );
+        #[cfg(target_arch = "wasm32")]
+        let handle =
+            raw_window_handle::RawDisplayHandle::Web(raw_window_handle::WebDisplayHandle::empty());
+
+        handle
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(pub(crate) u32);
+
+impl Id {
+    pub unsafe fn dummy() -> Id {
+        Id(0)
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct PlatformSpecificBuilderAttributes;
@@ -255,6 +255,7 @@ unsafe fn get_view_class(root_view_class: &'static Class) -> &'static Class {
                             id: touch_id,
                             location: (location.x as f64, location.y as f64).into(),
                             force,
+                            radius: None,
                             phase,
                         }),
                     }));
@@ -3,7 +3,7 @@
 use std::{collections::VecDeque, env, ffi::CStr, fmt, mem::MaybeUninit, os::raw::*, sync::Arc};
 
 use parking_lot::Mutex;
-use raw_window_handle::RawWindowHandle;
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
 use smithay_client_toolkit::reexports::client::ConnectError;
 
 pub use self::x11::XNotSupported;
@@ -15,7 +15,7 @@ use crate::{
     event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
     icon::Icon,
     monitor::{MonitorHandle as RootMonitorHandle, VideoMode as RootVideoMode},
-    window::{CursorIcon, Fullscreen, WindowAttributes},
+    window::{CursorGrabMode, CursorIcon, Fullscreen, WindowAttributes},
 };
 
 pub mod wayland;
@@ -245,6 +245,11 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        None
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         match self {
@@ -326,10 +331,10 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         match self {
-            &Window::X(ref window) => window.set_cursor_grab(grab),
-            &Window::Wayland(ref window) => window.set_cursor_grab(grab),
+            &Window::X(ref window) => window.set_cursor_grab(mode),
+            &Window::Wayland(ref window) => window.set_cursor_grab(mode),
         }
     }
 
@@ -405,6 +410,17 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn focus_window(&self) {
+        // Not implemented yet
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        // Not implemented yet
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         match self {
@@ -421,6 +437,11 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // Not implemented yet
+    }
+
     #[inline]
     pub fn request_redraw(&self) {
         match self {
@@ -471,6 +492,13 @@ impl Window {
             &Window::Wayland(ref window) => RawWindowHandle::Wayland(window.raw_window_handle()),
         }
     }
+
+    pub fn raw_display_handle(&self) -> RawDisplayHandle {
+        match self {
+            &Window::X(ref window) => RawDisplayHandle::Xlib(window.raw_display_handle()),
+            &Window::Wayland(ref window) => RawDisplayHandle::Wayland(window.raw_display_handle()),
+        }
+    }
 }
 
 unsafe extern "C" fn x_error_callback(
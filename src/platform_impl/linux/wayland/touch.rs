@@ -39,6 +39,7 @@ pub(crate) fn implement_touch(
                                     phase: TouchPhase::Started,
                                     location: (x, y).into(),
                                     force: None, // TODO
+                                    radius: None,
                                     id: id as u64,
                                 }),
                                 wid,
@@ -62,6 +63,7 @@ pub(crate) fn implement_touch(
                                     phase: TouchPhase::Ended,
                                     location: pt.location.into(),
                                     force: None, // TODO
+                                    radius: None,
                                     id: id as u64,
                                 }),
                                 pt.wid,
@@ -80,6 +82,7 @@ pub(crate) fn implement_touch(
                                     phase: TouchPhase::Moved,
                                     location: (x, y).into(),
                                     force: None, // TODO
+                                    radius: None,
                                     id: id as u64,
                                 }),
                                 pt.wid,
@@ -97,6 +100,7 @@ pub(crate) fn implement_touch(
                                     phase: TouchPhase::Cancelled,
                                     location: pt.location.into(),
                                     force: None, // TODO
+                                    radius: None,
                                     id: pt.id as u64,
                                 }),
                                 pt.wid,
@@ -1,4 +1,4 @@
-use raw_window_handle::unix::WaylandHandle;
+use raw_window_handle::{WaylandDisplayHandle, WaylandWindowHandle};
 use std::{
     collections::VecDeque,
     mem::replace,
@@ -14,7 +14,7 @@ use crate::{
         MonitorHandle as PlatformMonitorHandle,
         PlatformSpecificWindowBuilderAttributes as PlAttributes,
     },
-    window::{CursorIcon, Fullscreen, WindowAttributes},
+    window::{CursorGrabMode, CursorIcon, Fullscreen, WindowAttributes},
 };
 
 use smithay_client_toolkit::{
@@ -341,7 +341,14 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        let grab = match mode {
+            CursorGrabMode::None => false,
+            CursorGrabMode::Locked => true,
+            CursorGrabMode::Confined => {
+                return Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+        };
         *self.cursor_grab_changed.lock().unwrap() = Some(grab);
         Ok(())
     }
@@ -375,12 +382,16 @@ impl Window {
         primary_monitor(&self.outputs)
     }
 
-    pub fn raw_window_handle(&self) -> WaylandHandle {
-        WaylandHandle {
-            surface: self.surface().as_ref().c_ptr() as *mut _,
-            display: self.display().as_ref().c_ptr() as *mut _,
-            ..WaylandHandle::empty()
-        }
+    pub fn raw_window_handle(&self) -> WaylandWindowHandle {
+        let mut handle = WaylandWindowHandle::empty();
+        handle.surface = self.surface().as_ref().c_ptr() as *mut _;
+        handle
+    }
+
+    pub fn raw_display_handle(&self) -> WaylandDisplayHandle {
+        let mut handle = WaylandDisplayHandle::empty();
+        handle.display = self.display().as_ref().c_ptr() as *mut _;
+        handle
     }
 }
 
@@ -968,6 +968,7 @@ impl<T: 'static> EventProcessor<T> {
                                     phase,
                                     location,
                                     force: None, // TODO
+                                    radius: None,
                                     id,
                                 }),
                             })
@@ -1,4 +1,4 @@
-use raw_window_handle::unix::XlibHandle;
+use raw_window_handle::{XlibDisplayHandle, XlibWindowHandle};
 use std::{
     cmp,
     collections::HashSet,
@@ -23,7 +23,7 @@ use crate::{
         MonitorHandle as PlatformMonitorHandle, OsError, PlatformSpecificWindowBuilderAttributes,
         VideoMode as PlatformVideoMode,
     },
-    window::{CursorIcon, Fullscreen, Icon, WindowAttributes},
+    window::{CursorGrabMode, CursorIcon, Fullscreen, Icon, WindowAttributes},
 };
 
 use super::{ffi, util, EventLoopWindowTarget, ImeSender, WindowId, XConnection, XError};
@@ -1194,7 +1194,14 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        let grab = match mode {
+            CursorGrabMode::None => false,
+            CursorGrabMode::Confined => true,
+            CursorGrabMode::Locked => {
+                return Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+        };
         let mut grabbed_lock = self.cursor_grabbed.lock();
         if grab == *grabbed_lock {
             return Ok(());
@@ -1318,11 +1325,16 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn raw_window_handle(&self) -> XlibHandle {
-        XlibHandle {
-            window: self.xwindow,
-            display: self.xconn.display as _,
-            ..XlibHandle::empty()
-        }
+    pub fn raw_window_handle(&self) -> XlibWindowHandle {
+        let mut handle = XlibWindowHandle::empty();
+        handle.window = self.xwindow;
+        handle
+    }
+
+    pub fn raw_display_handle(&self) -> XlibDisplayHandle {
+        let mut handle = XlibDisplayHandle::empty();
+        handle.display = self.xconn.display as _;
+        handle.screen = self.screen_id;
+        handle
     }
 }
@@ -1,29 +1,40 @@
 pub use self::platform::*;
 
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", not(feature = "headless")))]
 #[path = "windows/mod.rs"]
 mod platform;
-#[cfg(any(
-    target_os = "linux",
-    target_os = "dragonfly",
-    target_os = "freebsd",
-    target_os = "netbsd",
-    target_os = "openbsd"
+#[cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ),
+    not(feature = "headless")
 ))]
 #[path = "linux/mod.rs"]
 mod platform;
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", not(feature = "headless")))]
 #[path = "macos/mod.rs"]
 mod platform;
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", not(feature = "headless")))]
 #[path = "android/mod.rs"]
 mod platform;
-#[cfg(target_os = "ios")]
+#[cfg(all(target_os = "ios", not(feature = "headless")))]
 #[path = "ios/mod.rs"]
 mod platform;
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", not(feature = "headless")))]
 #[path = "web/mod.rs"]
 mod platform;
+#[cfg(feature = "headless")]
+#[path = "headless/mod.rs"]
+mod platform;
+
+// The key mapping of the web backend doesn't depend on the browser, so it is tested on the host
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[path = "web/keyboard.rs"]
+mod web_keyboard;
 
 #[cfg(all(
     not(target_os = "ios"),
@@ -36,5 +47,6 @@ mod platform;
     not(target_os = "netbsd"),
     not(target_os = "openbsd"),
     not(target_arch = "wasm32"),
+    not(feature = "headless"),
 ))]
 compile_error!("The platform you're compiling for is not supported by winit");
@@ -0,0 +1,54 @@
+use crate::error::{ExternalError, NotSupportedError};
+use crate::event::WindowEvent;
+
+use super::backend;
+
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct Clipboard {
+    send_event: Rc<dyn Fn(WindowEvent)>,
+    is_user_gesture: Rc<dyn Fn() -> bool>,
+}
+
+impl Clipboard {
+    pub fn new(send_event: Rc<dyn Fn(WindowEvent)>, is_user_gesture: Rc<dyn Fn() -> bool>) -> Self {
+        Clipboard {
+            send_event,
+            is_user_gesture,
+        }
+    }
+
+    pub fn read_text(&self) -> Result<(), ExternalError> {
+        // Browsers only grant clipboard access while handling a user gesture. Rather than letting
+        // the request fail silently later on, it is refused right away.
+        if !(self.is_user_gesture)() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        let send_event = self.send_event.clone();
+        backend::clipboard::read_text(move |result| match result {
+            Ok(text) => send_event(WindowEvent::ReceivedClipboardText(text)),
+            Err(error) => {
+                warn!("Failed to read the clipboard: {:?}", error);
+                send_event(WindowEvent::ClipboardReadFailed);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn write_text(&self, text: &str) -> Result<(), ExternalError> {
+        if !(self.is_user_gesture)() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        backend::clipboard::write_text(text, |result| {
+            if let Err(error) = result {
+                warn!("Failed to write to the clipboard: {:?}", error);
+            }
+        });
+
+        Ok(())
+    }
+}
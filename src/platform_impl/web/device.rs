@@ -1,8 +1,47 @@
+use super::window;
+use crate::platform::web::PointerType;
+
+// Pointer ids are only unique within the document, so they are namespaced by the window whose
+// canvas received the event to keep the pointer streams of different canvases apart. Touch events
+// carry no pointer id, so all of their touches on a canvas share a device.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Id(pub i32);
+pub enum Id {
+    Dummy,
+    Pointer {
+        window: window::Id,
+        pointer_id: i32,
+        pointer_type: PointerType,
+    },
+    Touch(window::Id),
+    Gamepad(u32),
+}
 
 impl Id {
     pub unsafe fn dummy() -> Self {
-        Id(0)
+        Id::Dummy
+    }
+
+    pub fn pointer(window: window::Id, pointer_id: i32, pointer_type: PointerType) -> Self {
+        Id::Pointer {
+            window,
+            pointer_id,
+            pointer_type,
+        }
+    }
+
+    pub fn touch(window: window::Id) -> Self {
+        Id::Touch(window)
+    }
+
+    pub fn gamepad(index: u32) -> Self {
+        Id::Gamepad(index)
+    }
+
+    pub fn pointer_type(&self) -> Option<PointerType> {
+        match *self {
+            Id::Pointer { pointer_type, .. } => Some(pointer_type),
+            Id::Touch(_) => Some(PointerType::Touch),
+            Id::Dummy | Id::Gamepad(_) => None,
+        }
     }
 }
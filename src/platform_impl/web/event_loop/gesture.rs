@@ -0,0 +1,195 @@
+use crate::dpi::PhysicalPosition;
+use crate::event::TouchPhase;
+
+use instant::{Duration, Instant};
+
+// The longest a touch point may be down to count as a tap, and the longest time between the end
+// of a tap and the start of the next one for both to count as a double tap
+const TAP_DURATION: Duration = Duration::from_millis(300);
+// How far in logical pixels a tap may move, and how far the second tap of a double tap may be from
+// the first one
+const TAP_DISTANCE: f64 = 24.0;
+
+// Derives pinch and rotation gestures from the touch points of a window. A gesture lasts for as
+// long as the same two touch points are down; further touch points are ignored until one of them
+// is lifted.
+//
+// Every method returns the updates of the gesture, whose deltas are relative to the previous
+// update.
+#[derive(Default)]
+pub struct Gesture {
+    touches: Vec<(i32, PhysicalPosition<f64>)>,
+    // The distance and angle between the two touch points of the gesture in progress
+    pair: Option<(f64, f64)>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Update {
+    pub phase: TouchPhase,
+    // The change of the magnification, e.g. 0.1 when the distance grew by 10%
+    pub magnify: f64,
+    // The change of the angle in degrees, counterclockwise
+    pub rotate: f64,
+}
+
+impl Update {
+    pub fn new(phase: TouchPhase) -> Self {
+        Update {
+            phase,
+            magnify: 0.0,
+            rotate: 0.0,
+        }
+    }
+}
+
+impl Gesture {
+    pub fn is_active(&self) -> bool {
+        self.pair.is_some()
+    }
+
+    pub fn touch_start(&mut self, id: i32, location: PhysicalPosition<f64>) -> Vec<Update> {
+        self.touches.push((id, location));
+        self.start()
+    }
+
+    pub fn touch_move(&mut self, id: i32, location: PhysicalPosition<f64>) -> Vec<Update> {
+        let index = match self.touches.iter().position(|&(touch, _)| touch == id) {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        self.touches[index].1 = location;
+
+        let (previous_distance, previous_angle) = match self.pair {
+            Some(pair) if index < 2 => pair,
+            _ => return Vec::new(),
+        };
+        let (distance, angle) = self.measure();
+        self.pair = Some((distance, angle));
+
+        // Touch points on top of each other have no scale to compare with
+        let magnify = if previous_distance > 0.0 {
+            distance / previous_distance - 1.0
+        } else {
+            0.0
+        };
+        // The y axis points down, so a growing angle is a clockwise rotation. The change is
+        // wrapped to the shorter direction, as the angle jumps by 360 degrees at its boundary.
+        let mut rotate = previous_angle - angle;
+        if rotate > 180.0 {
+            rotate -= 360.0;
+        } else if rotate < -180.0 {
+            rotate += 360.0;
+        }
+
+        vec![Update {
+            phase: TouchPhase::Moved,
+            magnify,
+            rotate,
+        }]
+    }
+
+    // Lifting either touch point of the gesture ends it, and a new one starts right away if two
+    // touch points remain
+    pub fn touch_end(&mut self, id: i32, phase: TouchPhase) -> Vec<Update> {
+        let index = match self.touches.iter().position(|&(touch, _)| touch == id) {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        self.touches.remove(index);
+
+        if index >= 2 || self.pair.take().is_none() {
+            return Vec::new();
+        }
+        let mut updates = vec![Update::new(phase)];
+        updates.extend(self.start());
+        updates
+    }
+
+    fn start(&mut self) -> Vec<Update> {
+        if self.pair.is_some() || self.touches.len() < 2 {
+            return Vec::new();
+        }
+        self.pair = Some(self.measure());
+        vec![Update::new(TouchPhase::Started)]
+    }
+
+    fn measure(&self) -> (f64, f64) {
+        let (a, b) = (self.touches[0].1, self.touches[1].1);
+        let (x, y) = (b.x - a.x, b.y - a.y);
+        (x.hypot(y), y.atan2(x).to_degrees())
+    }
+}
+
+// Detects double taps: two quick taps of a single touch point close to each other. Touch points
+// that are held down, move away, or are joined by another one are no taps.
+#[derive(Default)]
+pub struct DoubleTap {
+    // The touch point that may be a tap, with where and when it started
+    tap: Option<(i32, PhysicalPosition<f64>, Instant)>,
+    // Where and when the previous tap ended
+    previous_tap: Option<(PhysicalPosition<f64>, Instant)>,
+    touches: usize,
+}
+
+impl DoubleTap {
+    pub fn touch_start(&mut self, id: i32, location: PhysicalPosition<f64>) {
+        self.touches += 1;
+        if self.touches == 1 {
+            self.tap = Some((id, location, Instant::now()));
+        } else {
+            self.tap = None;
+            self.previous_tap = None;
+        }
+    }
+
+    pub fn touch_move(&mut self, id: i32, location: PhysicalPosition<f64>, scale_factor: f64) {
+        if let Some((tap, start, _)) = self.tap {
+            if tap == id && distance(start, location) > TAP_DISTANCE * scale_factor {
+                self.tap = None;
+            }
+        }
+    }
+
+    // Returns whether the touch point completed a double tap
+    pub fn touch_end(
+        &mut self,
+        id: i32,
+        location: PhysicalPosition<f64>,
+        phase: TouchPhase,
+        scale_factor: f64,
+    ) -> bool {
+        self.touches = self.touches.saturating_sub(1);
+        let (start, time) = match self.tap {
+            Some((tap, start, time)) if tap == id => (start, time),
+            _ => return false,
+        };
+        self.tap = None;
+
+        let now = Instant::now();
+        if phase != TouchPhase::Ended
+            || now - time > TAP_DURATION
+            || distance(start, location) > TAP_DISTANCE * scale_factor
+        {
+            self.previous_tap = None;
+            return false;
+        }
+
+        match self.previous_tap.take() {
+            Some((previous, end))
+                if time - end <= TAP_DURATION
+                    && distance(previous, start) <= TAP_DISTANCE * scale_factor =>
+            {
+                true
+            }
+            // The second tap of a double tap doesn't start another one
+            _ => {
+                self.previous_tap = Some((location, now));
+                false
+            }
+        }
+    }
+}
+
+fn distance(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
@@ -1,3 +1,4 @@
+mod gesture;
 mod proxy;
 mod runner;
 mod state;
@@ -10,7 +11,7 @@ use super::{backend, device, monitor, window};
 use crate::event::Event;
 use crate::event_loop as root;
 
-use std::collections::{vec_deque::IntoIter as VecDequeIter, VecDeque};
+use std::iter;
 use std::marker::PhantomData;
 
 pub struct EventLoop<T: 'static> {
@@ -27,8 +28,8 @@ impl<T> EventLoop<T> {
         }
     }
 
-    pub fn available_monitors(&self) -> VecDequeIter<monitor::Handle> {
-        VecDeque::new().into_iter()
+    pub fn available_monitors(&self) -> iter::Once<monitor::Handle> {
+        iter::once(monitor::Handle)
     }
 
     pub fn primary_monitor(&self) -> monitor::Handle {
@@ -50,6 +51,38 @@ impl<T> EventLoop<T> {
         }));
     }
 
+    pub fn run_with_exit<F, G>(self, event_handler: F, on_exit: G)
+    where
+        F: 'static + FnMut(Event<T>, &root::EventLoopWindowTarget<T>, &mut root::ControlFlow),
+        G: 'static + FnOnce(),
+    {
+        // Set before running, as the event loop may exit during initialization
+        self.elw.p.set_on_exit(Box::new(on_exit));
+        self.run(event_handler)
+    }
+
+    pub fn pump_events<F>(&mut self, mut event_handler: F) -> root::ControlFlow
+    where
+        F: FnMut(Event<T>, &root::EventLoopWindowTarget<T>, &mut root::ControlFlow),
+    {
+        let target = &self.elw;
+        target
+            .p
+            .pump_events(&mut |event, flow| event_handler(event, target, flow))
+    }
+
+    pub fn set_container(&self, container: Option<backend::RawContainerType>) {
+        self.elw.p.options().container = container;
+    }
+
+    pub fn set_passive_listeners(&self, passive: bool) {
+        self.elw.p.options().passive_listeners = passive;
+    }
+
+    pub fn set_prevent_default(&self, prevent_default: bool) {
+        self.elw.p.options().prevent_default = prevent_default;
+    }
+
     pub fn create_proxy(&self) -> Proxy<T> {
         self.elw.p.proxy()
     }
@@ -58,3 +91,9 @@ impl<T> EventLoop<T> {
         &self.elw
     }
 }
+
+impl<T> Drop for EventLoop<T> {
+    fn drop(&mut self) {
+        self.elw.p.drop_event_loop();
+    }
+}
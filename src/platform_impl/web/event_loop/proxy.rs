@@ -1,5 +1,4 @@
-use super::runner;
-use crate::event::Event;
+use super::{backend, runner};
 use crate::event_loop::EventLoopClosed;
 
 pub struct Proxy<T: 'static> {
@@ -11,9 +10,26 @@ impl<T: 'static> Proxy<T> {
         Proxy { runner }
     }
 
+    // User events share the queue of the runner with the events of the page, so they are handled
+    // in the order they were sent, and a waiting event loop is woken up immediately
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
-        self.runner.send_event(Event::UserEvent(event));
-        Ok(())
+        self.runner.send_user_event(event).map_err(EventLoopClosed)
+    }
+
+    // Messages of the worker are only received on the main thread, which makes this a `Send`
+    // alternative to the proxy itself. The listener is removed once the event loop exits.
+    pub fn listen_to_worker<F>(&self, worker: &backend::Worker, mut deserialize: F)
+    where
+        F: 'static + FnMut(backend::WorkerMessage) -> Option<T>,
+    {
+        let proxy = self.clone();
+        let listener = backend::on_worker_message(worker, move |message| {
+            if let Some(event) = deserialize(message) {
+                // Messages can no longer arrive once the event loop has exited
+                let _ = proxy.send_event(event);
+            }
+        });
+        self.runner.add_worker_listener(listener);
     }
 }
 
@@ -1,19 +1,28 @@
-use super::{backend, state::State};
-use crate::event::{Event, StartCause};
+use super::{backend, device, state::State};
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event::{DeviceEvent, DeviceId, Event, ModifiersState, StartCause, WindowEvent};
 use crate::event_loop as root;
 use crate::window::WindowId;
 
 use instant::{Duration, Instant};
+#[cfg(feature = "trace")]
+use std::fmt;
 use std::{
     cell::{RefCell, Cell},
     clone::Clone,
-    collections::{HashSet, VecDeque},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     iter,
-    rc::Rc,
+    rc::{Rc, Weak},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 pub struct Shared<T: 'static>(Rc<Execution<T>>);
 
+// Applies the size of a window once its `ScaleFactorChanged` has been handled, which is the
+// suggested one unless another one was set with `Window::set_inner_size` meanwhile, and returns
+// the new size of the canvas if it changed
+pub type ApplySize = Box<dyn FnOnce(Option<PhysicalSize<u32>>) -> Option<PhysicalSize<u32>>>;
+
 impl<T> Clone for Shared<T> {
     fn clone(&self) -> Self {
         Shared(self.0.clone())
@@ -23,10 +32,52 @@ impl<T> Clone for Shared<T> {
 pub struct Execution<T: 'static> {
     runner: RefCell<Option<Runner<T>>>,
     events: RefCell<VecDeque<Event<T>>>,
-    id: RefCell<u32>,
-    redraw_pending: RefCell<HashSet<WindowId>>,
+    // The events sent through proxies that haven't been handled yet, which are bounded
+    user_events: Cell<usize>,
+    // Ordered by id, so that windows are redrawn in the order they were created
+    redraw_pending: RefCell<BTreeSet<WindowId>>,
+    // Windows whose canvas is hidden until their first `RedrawRequested` has been handled
+    unrevealed: RefCell<HashSet<WindowId>>,
     redraw_requested: Cell<bool>,
-    request_animation_frame: RefCell<Option<backend::AnimationFrame>>
+    is_page_hidden: Cell<bool>,
+    frame_timestamp: Cell<Option<f64>>,
+    titles: RefCell<HashMap<WindowId, String>>,
+    focused_window: Cell<Option<WindowId>>,
+    // The title the page shows while it isn't flashed to request attention
+    page_title: RefCell<Option<String>>,
+    // The window requesting attention, whether the page currently shows its marked title, and
+    // the timeout toggling it
+    attention: Cell<Option<WindowId>>,
+    is_title_marked: Cell<bool>,
+    attention_timeout: RefCell<Option<backend::Timeout>>,
+    modifiers: Cell<ModifiersState>,
+    cursor_moves: RefCell<Vec<(WindowId, DeviceId, Event<T>)>>,
+    resizes: RefCell<Vec<(WindowId, PhysicalSize<u32>)>>,
+    moves: RefCell<HashSet<WindowId>>,
+    positions: RefCell<HashMap<WindowId, PhysicalPosition<u32>>>,
+    cursor_positions: RefCell<HashMap<WindowId, PhysicalPosition<f64>>>,
+    request_animation_frame: RefCell<Option<backend::AnimationFrame>>,
+    frame_interval: Cell<Option<Duration>>,
+    last_frame: Cell<Option<Instant>>,
+    frame_timeout: RefCell<Option<backend::Timeout>>,
+    gamepads: RefCell<Option<backend::Gamepads>>,
+    all_canvases: RefCell<Vec<(WindowId, Weak<RefCell<backend::Canvas>>)>>,
+    worker_listeners: RefCell<Vec<backend::WorkerListener>>,
+    on_exit: RefCell<Option<Box<dyn FnOnce()>>>,
+    is_suspended: Cell<bool>,
+    // Whether events have been pumped, by an event loop that is driven by the application
+    pumped: Cell<bool>,
+    // Whether the event loop was dropped without running, which leaves no listener for the events
+    is_abandoned: Cell<bool>,
+    // The `key` of the browser's keyboard events, in the order their `KeyboardInput` events were
+    // sent, and the one of the `KeyboardInput` event handled last
+    keys: RefCell<VecDeque<Option<String>>>,
+    current_key: RefCell<Option<String>>,
+    // The functions applying the sizes suggested by `ScaleFactorChanged` events, in the order the
+    // events were sent, which are `None` for synthetic events, and the window whose event is
+    // handled along with the size set for it meanwhile
+    apply_sizes: RefCell<VecDeque<Option<ApplySize>>>,
+    new_inner_size: Cell<Option<(WindowId, Option<PhysicalSize<u32>>)>>,
 }
 
 struct Runner<T: 'static> {
@@ -50,10 +101,39 @@ impl<T: 'static> Shared<T> {
         Shared(Rc::new(Execution {
             runner: RefCell::new(None),
             events: RefCell::new(VecDeque::new()),
-            id: RefCell::new(0),
-            redraw_pending: RefCell::new(HashSet::new()),
+            user_events: Cell::new(0),
+            redraw_pending: RefCell::new(BTreeSet::new()),
+            unrevealed: RefCell::new(HashSet::new()),
             redraw_requested: Cell::new(false),
+            is_page_hidden: Cell::new(backend::is_hidden()),
+            frame_timestamp: Cell::new(None),
+            titles: RefCell::new(HashMap::new()),
+            focused_window: Cell::new(None),
+            page_title: RefCell::new(None),
+            attention: Cell::new(None),
+            is_title_marked: Cell::new(false),
+            attention_timeout: RefCell::new(None),
+            modifiers: Cell::new(ModifiersState::empty()),
+            cursor_moves: RefCell::new(Vec::new()),
+            resizes: RefCell::new(Vec::new()),
+            moves: RefCell::new(HashSet::new()),
+            positions: RefCell::new(HashMap::new()),
+            cursor_positions: RefCell::new(HashMap::new()),
             request_animation_frame: RefCell::default(),
+            frame_interval: Cell::new(None),
+            last_frame: Cell::new(None),
+            frame_timeout: RefCell::new(None),
+            gamepads: RefCell::default(),
+            all_canvases: RefCell::new(Vec::new()),
+            worker_listeners: RefCell::new(Vec::new()),
+            on_exit: RefCell::new(None),
+            is_suspended: Cell::new(false),
+            pumped: Cell::new(false),
+            is_abandoned: Cell::new(false),
+            keys: RefCell::new(VecDeque::new()),
+            current_key: RefCell::new(None),
+            apply_sizes: RefCell::new(VecDeque::new()),
+            new_inner_size: Cell::new(None),
         }))
     }
 
@@ -72,25 +152,504 @@ impl<T: 'static> Shared<T> {
         *self.0.request_animation_frame.borrow_mut() = Some(animation_frame);
     }
 
+    pub fn set_gamepads(&self, gamepads: backend::Gamepads) {
+        *self.0.gamepads.borrow_mut() = Some(gamepads);
+    }
+
+    // Set a callback to run once the event loop has exited and released its resources
+    pub fn set_on_exit(&self, on_exit: Box<dyn FnOnce()>) {
+        *self.0.on_exit.borrow_mut() = Some(on_exit);
+    }
+
+    // Keep track of the canvases using this runner, so their listeners can be removed when the
+    // event loop exits
+    pub fn add_canvas(&self, id: WindowId, canvas: &Rc<RefCell<backend::Canvas>>) {
+        self.0
+            .all_canvases
+            .borrow_mut()
+            .push((id, Rc::downgrade(canvas)));
+    }
+
+    pub fn add_worker_listener(&self, listener: backend::WorkerListener) {
+        self.0.worker_listeners.borrow_mut().push(listener);
+    }
+
+    // Called when the event loop is dropped, which is after the listener was set if it ran
+    //
+    // Otherwise, nothing will ever handle the events, so the queued ones are discarded and new
+    // ones are refused
+    pub fn drop_event_loop(&self) {
+        if self.0.runner.borrow().is_some() {
+            return;
+        }
+        self.0.is_abandoned.set(true);
+        self.0.events.borrow_mut().clear();
+        self.0.worker_listeners.borrow_mut().clear();
+    }
+
     // Generate a strictly increasing ID
     // This is used to differentiate windows when handling events
+    //
+    // IDs are shared by all event loops, as they also identify the canvases in the document through
+    // their `data-raw-handle` attribute. They start at 1, as 0 is reserved by `raw-window-handle`.
     pub fn generate_id(&self) -> u32 {
-        let mut id = self.0.id.borrow_mut();
-        *id += 1;
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
 
-        *id
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
     }
 
     pub fn request_redraw(&self, id: WindowId) {
         self.0.redraw_pending.borrow_mut().insert(id);
-        if self.0.redraw_requested.replace(true) {
-            self.0.request_animation_frame.borrow().as_ref().unwrap().request_animation_frame();
+        self.request_animation_frame();
+    }
+
+    pub fn reveal_after_redraw(&self, id: WindowId) {
+        self.0.unrevealed.borrow_mut().insert(id);
+    }
+
+    // Show the canvas of a window hidden until ready, once its first frame has been drawn
+    fn reveal(&self, id: WindowId) {
+        if !self.0.unrevealed.borrow_mut().remove(&id) {
+            return;
+        }
+        let canvas = self
+            .0
+            .all_canvases
+            .borrow()
+            .iter()
+            .find(|&&(window_id, _)| window_id == id)
+            .and_then(|(_, canvas)| canvas.upgrade());
+        if let Some(canvas) = canvas {
+            if let Ok(canvas) = canvas.try_borrow() {
+                canvas.reveal();
+            }
+        }
+    }
+
+    // Schedule a single animation frame, however many times this is called before it runs
+    //
+    // No frames are requested while the page is hidden, they are requested again once it is
+    // visible if anything still needs one
+    pub fn request_animation_frame(&self) {
+        if self.0.is_page_hidden.get() {
+            return;
+        }
+        if !self.0.redraw_requested.replace(true) {
+            let delay = self.frame_delay();
+            #[cfg(feature = "trace")]
+            trace!(
+                "Requesting an animation frame, after {:?}",
+                delay.unwrap_or_default()
+            );
+            match delay {
+                // Waiting for the frame with a timeout rather than skipping frames keeps the
+                // browser from running the animation frame callback at its full rate
+                Some(delay) => {
+                    let runner = self.clone();
+                    let timeout = backend::Timeout::new(move || runner.request_frame(), delay);
+                    *self.0.frame_timeout.borrow_mut() = Some(timeout);
+                }
+                None => self.request_frame(),
+            }
+        }
+    }
+
+    fn request_frame(&self) {
+        // The animation frame is dropped once the event loop has exited
+        if let Some(ref animation_frame) = *self.0.request_animation_frame.borrow() {
+            animation_frame.request_animation_frame();
+        }
+    }
+
+    // The time to wait before requesting the next frame to stay below the maximum frame rate
+    //
+    // The frame is requested a little ahead of time, so that it lands on the refresh of the
+    // display the interval ends in rather than on the one after it
+    fn frame_delay(&self) -> Option<Duration> {
+        const FRAME_SLACK: Duration = Duration::from_millis(4);
+
+        let due = self.0.last_frame.get()? + self.0.frame_interval.get()?;
+        let now = Instant::now();
+        if due > now + FRAME_SLACK {
+            Some(due - now - FRAME_SLACK)
+        } else {
+            None
+        }
+    }
+
+    // A pending frame is cancelled and requested again, so that it is throttled right away
+    //
+    // Frame rates that aren't positive and finite don't cap anything, and the interval is limited
+    // to the longest delay browsers support for timeouts
+    pub fn set_max_frame_rate(&self, max_frame_rate: Option<f64>) {
+        const MAX_FRAME_INTERVAL: Duration = Duration::from_millis(i32::MAX as u64);
+
+        let frame_interval = max_frame_rate
+            .filter(|max_frame_rate| max_frame_rate.is_finite() && *max_frame_rate > 0.0)
+            .map(|max_frame_rate| {
+                let interval = (1.0 / max_frame_rate).min(MAX_FRAME_INTERVAL.as_secs_f64());
+                Duration::from_secs_f64(interval)
+            });
+        self.0.frame_interval.set(frame_interval);
+
+        if self.0.redraw_requested.replace(false) {
+            self.0.frame_timeout.borrow_mut().take();
+            if let Some(ref animation_frame) = *self.0.request_animation_frame.borrow() {
+                animation_frame.cancel_animation_frame();
+            }
+            self.request_animation_frame();
+        }
+    }
+
+    pub fn set_page_hidden(&self, is_hidden: bool) {
+        let was_hidden = self.0.is_page_hidden.replace(is_hidden);
+        if was_hidden && !is_hidden && self.needs_animation_frame() {
+            self.request_animation_frame();
+        }
+    }
+
+    fn needs_animation_frame(&self) -> bool {
+        let is_gamepad_connected = match *self.0.gamepads.borrow() {
+            Some(ref gamepads) => gamepads.is_connected(),
+            None => false,
+        };
+        is_gamepad_connected
+            || !self.0.redraw_pending.borrow().is_empty()
+            || !self.0.cursor_moves.borrow().is_empty()
+            || !self.0.resizes.borrow().is_empty()
+            || !self.0.moves.borrow().is_empty()
+    }
+
+    // The timestamp the browser passed to the latest animation frame, in milliseconds since the
+    // time origin of the page, like `performance.now()`
+    pub fn frame_timestamp(&self) -> Option<f64> {
+        self.0.frame_timestamp.get()
+    }
+
+    // Send `KeyboardInput` events along with the `key` of the keyboard events behind them, which
+    // is `None` for synthetic events
+    pub fn send_keyboard_inputs(&self, inputs: impl Iterator<Item = (Event<T>, Option<String>)>) {
+        if self.is_closed() {
+            return;
+        }
+        let (events, keys): (Vec<_>, Vec<_>) = inputs.unzip();
+        self.0.keys.borrow_mut().extend(keys);
+        self.send_events(events.into_iter());
+    }
+
+    pub fn current_key(&self) -> Option<String> {
+        self.0.current_key.borrow().clone()
+    }
+
+    // Send `ScaleFactorChanged` along with the function applying the size of the window once the
+    // event has been handled, which is `None` for synthetic events
+    pub fn send_scale_factor_changed(
+        &self,
+        window_id: WindowId,
+        scale_factor: f64,
+        new_inner_size: PhysicalSize<u32>,
+        apply_size: Option<ApplySize>,
+    ) {
+        if self.is_closed() {
+            return;
+        }
+        self.0.apply_sizes.borrow_mut().push_back(apply_size);
+        self.send_event(Event::WindowEvent {
+            window_id,
+            event: WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            },
+        });
+    }
+
+    // The size set for a window while its `ScaleFactorChanged` is handled replaces the suggested
+    // one, and is only applied once the handler returns. Returns whether the size was held back.
+    pub fn override_inner_size(&self, window_id: WindowId, size: PhysicalSize<u32>) -> bool {
+        match self.0.new_inner_size.get() {
+            Some((id, _)) if id == window_id => {
+                self.0.new_inner_size.set(Some((id, Some(size))));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Run the logic for an animation frame, which samples the gamepads and redraws the windows
+    pub fn animation_frame(&self, timestamp: f64) {
+        self.0.redraw_requested.set(false);
+        self.0.last_frame.set(Some(Instant::now()));
+
+        // Browsers pass the same timestamp to every callback of a frame, but it is kept from ever
+        // going backwards
+        let timestamp = match self.0.frame_timestamp.get() {
+            Some(previous) if previous > timestamp => previous,
+            _ => timestamp,
+        };
+        self.0.frame_timestamp.set(Some(timestamp));
+        #[cfg(feature = "trace")]
+        trace!("Running the animation frame at {}ms", timestamp);
+
+        self.poll_gamepads();
+        self.send_resizes();
+        self.send_moves();
+        self.send_cursor_moves();
+
+        // Frames requested for anything but a redraw, e.g. to sample the gamepads, don't emit
+        // `RedrawEventsCleared` while waiting, so an idle application isn't woken up every frame
+        let is_polling = self.current_control_flow() == root::ControlFlow::Poll;
+        if is_polling || !self.0.redraw_pending.borrow().is_empty() {
+            self.redraw();
+        }
+
+        // Gamepads have to be sampled every frame for as long as any of them is connected
+        if self.needs_animation_frame() && !self.is_closed() {
+            self.request_animation_frame();
+        }
+    }
+
+    fn poll_gamepads(&self) {
+        let events = match *self.0.gamepads.borrow_mut() {
+            Some(ref mut gamepads) => gamepads.poll(),
+            None => return,
+        };
+
+        if !events.is_empty() {
+            self.send_events(events.into_iter().map(|(index, event)| Event::DeviceEvent {
+                device_id: DeviceId(device::Id::gamepad(index)),
+                event,
+            }));
+        }
+    }
+
+    // The page has a single title, which is the title of the most recently focused window, or of
+    // the window it was set for if no window has been focused yet
+    pub fn set_title(&self, id: WindowId, title: &str) {
+        self.0.titles.borrow_mut().insert(id, title.to_owned());
+
+        match self.0.focused_window.get() {
+            Some(focused_window) if focused_window != id => (),
+            _ => self.show_title(title),
+        }
+    }
+
+    pub fn focus_window(&self, id: WindowId) {
+        self.0.focused_window.set(Some(id));
+        if self.0.attention.get() == Some(id) {
+            self.stop_attention();
+        }
+
+        let title = self.0.titles.borrow().get(&id).cloned();
+        if let Some(title) = title {
+            self.show_title(&title);
+        }
+    }
+
+    // While the title is flashed, the page title is only shown once the flashing stops
+    fn show_title(&self, title: &str) {
+        *self.0.page_title.borrow_mut() = Some(title.to_owned());
+        if !self.0.is_title_marked.get() {
+            backend::set_title(title);
+        }
+    }
+
+    // Flashes the page title, alternating between the title of the window and the page title,
+    // until the window is focused. A later request from another window takes over the flashing.
+    pub fn request_attention(&self, id: WindowId) {
+        if self.0.attention.replace(Some(id)).is_none() && !self.is_closed() {
+            self.flash_title();
+        }
+    }
+
+    fn flash_title(&self) {
+        const ATTENTION_MARKER: &str = "(*)";
+        const FLASH_INTERVAL: Duration = Duration::from_millis(1000);
+
+        let id = match self.0.attention.get() {
+            Some(id) => id,
+            None => return,
+        };
+        if self
+            .0
+            .is_title_marked
+            .replace(!self.0.is_title_marked.get())
+        {
+            if let Some(ref title) = *self.0.page_title.borrow() {
+                backend::set_title(title);
+            }
+        } else {
+            let title = match self.0.titles.borrow().get(&id) {
+                Some(title) => format!("{} {}", ATTENTION_MARKER, title),
+                None => ATTENTION_MARKER.to_owned(),
+            };
+            backend::set_title(&title);
+        }
+
+        let runner = self.clone();
+        let timeout = backend::Timeout::new(move || runner.flash_title(), FLASH_INTERVAL);
+        *self.0.attention_timeout.borrow_mut() = Some(timeout);
+    }
+
+    fn stop_attention(&self) {
+        self.0.attention.set(None);
+        let timeout = self.0.attention_timeout.borrow_mut().take();
+        drop(timeout);
+        if self.0.is_title_marked.replace(false) {
+            if let Some(ref title) = *self.0.page_title.borrow() {
+                backend::set_title(title);
+            }
+        }
+    }
+
+    // Modifiers are read from every keyboard and pointer event, but `ModifiersChanged` is only sent
+    // when they differ from the last known state, e.g. when they were changed outside the canvas
+    pub fn set_modifiers(&self, modifiers: ModifiersState) {
+        if self.0.modifiers.replace(modifiers) != modifiers {
+            self.send_event(Event::DeviceEvent {
+                device_id: DeviceId(unsafe { device::Id::dummy() }),
+                event: DeviceEvent::ModifiersChanged(modifiers),
+            });
         }
     }
 
+    // Cursor moves of windows coalescing them per frame are held back until the next animation
+    // frame, keeping only the most recent one of each pointer
+    pub fn coalesce_cursor_move(&self, window_id: WindowId, device_id: DeviceId, event: Event<T>) {
+        {
+            let mut cursor_moves = self.0.cursor_moves.borrow_mut();
+            match cursor_moves
+                .iter_mut()
+                .find(|&&mut (window, device, _)| window == window_id && device == device_id)
+            {
+                Some(cursor_move) => cursor_move.2 = event,
+                None => cursor_moves.push((window_id, device_id, event)),
+            }
+        }
+        self.request_animation_frame();
+    }
+
+    // Send the cursor moves held back so far, which has to happen before any other pointer event
+    // to keep the events in order
+    pub fn send_cursor_moves(&self) {
+        let cursor_moves = std::mem::take(&mut *self.0.cursor_moves.borrow_mut());
+        if !cursor_moves.is_empty() {
+            self.send_events(cursor_moves.into_iter().map(|(_, _, event)| event));
+        }
+    }
+
+    // Resizes are held back until the next animation frame as well, so dragging the size of a
+    // canvas emits a single `Resized` per frame with the final size, ahead of `RedrawRequested`
+    pub fn coalesce_resize(&self, window_id: WindowId, size: PhysicalSize<u32>) {
+        {
+            let mut resizes = self.0.resizes.borrow_mut();
+            match resizes
+                .iter_mut()
+                .find(|&&mut (window, _)| window == window_id)
+            {
+                Some(resize) => resize.1 = size,
+                None => resizes.push((window_id, size)),
+            }
+        }
+        self.request_animation_frame();
+    }
+
+    // Send the resizes held back so far, which has to happen before any event that depends on
+    // the size of the window, like `ScaleFactorChanged`
+    pub fn send_resizes(&self) {
+        let resizes = std::mem::take(&mut *self.0.resizes.borrow_mut());
+        if !resizes.is_empty() {
+            self.send_events(
+                resizes
+                    .into_iter()
+                    .map(|(window_id, size)| Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::Resized(size),
+                    }),
+            );
+        }
+    }
+
+    // Scrolling and changes of the layout may move a window, whose position is compared with the
+    // one reported last in the next animation frame, once the layout has settled
+    pub fn coalesce_move(&self, window_id: WindowId) {
+        self.0.moves.borrow_mut().insert(window_id);
+        self.request_animation_frame();
+    }
+
+    // Send `Moved` for the windows held back so far whose position actually changed
+    pub fn send_moves(&self) {
+        let moves = std::mem::take(&mut *self.0.moves.borrow_mut());
+        if moves.is_empty() {
+            return;
+        }
+
+        let mut events = Vec::new();
+        for (id, canvas) in self.0.all_canvases.borrow().iter() {
+            if !moves.contains(id) {
+                continue;
+            }
+            let canvas = match canvas.upgrade() {
+                Some(canvas) => canvas,
+                None => continue,
+            };
+            // `Moved` can't hold negative coordinates, which are clamped to zero when the canvas
+            // is scrolled past the top or left of the viewport
+            let position = match canvas.try_borrow() {
+                Ok(canvas) => canvas.outer_position().to_physical(canvas.scale_factor()),
+                Err(_) => continue,
+            };
+            if self.0.positions.borrow_mut().insert(*id, position) != Some(position) {
+                events.push(Event::WindowEvent {
+                    window_id: *id,
+                    event: WindowEvent::Moved(position),
+                });
+            }
+        }
+        if !events.is_empty() {
+            self.send_events(events.into_iter());
+        }
+    }
+
+    // The most recent cursor position over each window, which is `None` once the cursor left it
+    pub fn set_cursor_position(&self, id: WindowId, position: Option<PhysicalPosition<f64>>) {
+        let mut cursor_positions = self.0.cursor_positions.borrow_mut();
+        match position {
+            Some(position) => cursor_positions.insert(id, position),
+            None => cursor_positions.remove(&id),
+        };
+    }
+
+    pub fn cursor_position(&self, id: WindowId) -> Option<PhysicalPosition<f64>> {
+        self.0.cursor_positions.borrow().get(&id).copied()
+    }
+
+    // The application is resumed right away, as the page is ready to be rendered to
     pub fn init(&self) {
         let start_cause = Event::NewEvents(StartCause::Init);
-        self.run_until_cleared(iter::once(start_cause));
+        self.run_until_cleared(iter::once(start_cause).chain(iter::once(Event::Resumed)));
+    }
+
+    // The page can be frozen right after it is suspended, which stops the timeouts of polling, so
+    // the event is handled right away unless another event is being handled
+    pub fn suspend(&self) {
+        if self.is_closed() || self.0.is_suspended.replace(true) {
+            return;
+        }
+        let is_busy = match *self.0.runner.borrow() {
+            Some(ref runner) => runner.is_busy,
+            None => true,
+        };
+        if is_busy {
+            self.send_event(Event::Suspended);
+        } else {
+            self.process_events(iter::once(Event::Suspended));
+        }
+    }
+
+    pub fn resume(&self) {
+        if self.0.is_suspended.replace(false) {
+            self.send_event(Event::Resumed);
+        }
     }
 
     // Run the polling logic for the Poll ControlFlow, which involves clearing the queue
@@ -116,6 +675,24 @@ impl<T: 'static> Shared<T> {
         self.send_events(iter::once(event));
     }
 
+    // Add an event sent through a proxy to the event loop runner
+    //
+    // The event is handed back if no listener is left to handle it, i.e. the event loop has
+    // exited or was dropped without running, as it would be discarded otherwise, and if too many
+    // are queued. Only the events of proxies are bounded, and only they count towards the bound:
+    // the events of the page can't be handed back, and stop arriving while the event handler
+    // blocks the page anyway.
+    pub fn send_user_event(&self, event: T) -> Result<(), T> {
+        const USER_EVENT_CAPACITY: usize = 4096;
+
+        if self.is_closed() || self.0.user_events.get() >= USER_EVENT_CAPACITY {
+            return Err(event);
+        }
+        self.0.user_events.set(self.0.user_events.get() + 1);
+        self.send_event(Event::UserEvent(event));
+        Ok(())
+    }
+
     // Add a series of events to the event loop runner
     //
     // It will determine if the event should be immediately sent to the user or buffered for later
@@ -141,9 +718,23 @@ impl<T: 'static> Shared<T> {
         }
         if !process_immediately {
             // Queue these events to look at later
-            self.0.events.borrow_mut().extend(events);
+            let mut queue = self.0.events.borrow_mut();
+            #[cfg(feature = "trace")]
+            let queued = queue.len();
+            queue.extend(events);
+            #[cfg(feature = "trace")]
+            trace!(
+                "Queued {} events until the runner can handle them",
+                queue.len() - queued
+            );
             return;
         }
+        self.process_events(events);
+    }
+
+    // Run an iteration of the event loop for the given events, which the runner must be able to
+    // process right away
+    fn process_events(&self, events: impl Iterator<Item = Event<T>>) {
         // At this point, we know this is a fresh set of events
         // Now we determine why new events are incoming, and handle the events
         let start_cause = if let Some(runner) = &*self.0.runner.borrow() {
@@ -166,10 +757,12 @@ impl<T: 'static> Shared<T> {
         } else {
             unreachable!("The runner cannot process events when it is not attached");
         };
-        // Take the start event, then the events provided to this function, and run an iteration of
-        // the event loop
+        // Take the start event, then any events still queued and the events provided to this
+        // function, and run an iteration of the event loop. The queued events go first, so that
+        // events are always handled in the order they were sent.
         let start_event = Event::NewEvents(start_cause);
-        let events = iter::once(start_event).chain(events);
+        let queued_events: Vec<_> = self.0.events.borrow_mut().drain(..).collect();
+        let events = iter::once(start_event).chain(queued_events).chain(events);
         self.run_until_cleared(events);
     }
 
@@ -188,38 +781,143 @@ impl<T: 'static> Shared<T> {
         // If the event loop is closed, it has been closed this iteration and now the closing
         // event should be emitted
         if self.is_closed() {
-            self.handle_event(Event::LoopDestroyed, &mut control);
+            self.handle_loop_destroyed(&mut control);
         }
     }
 
     pub fn redraw(&self) {
-        // Collect all of the redraw events to avoid double-locking the RefCell
-        let redraw_events: Vec<WindowId> = self.0.redraw_pending.borrow_mut().drain().collect();
+        // Nothing should be drawn once the event loop has been closed, and an event loop that
+        // isn't run draws when its events are pumped
+        if self.is_closed() || self.0.runner.borrow().is_none() {
+            return;
+        }
+        // Every window requesting a redraw gets a single `RedrawRequested` per frame, however often
+        // it asked for one. Redraws requested while handling these are left for the next frame.
+        let redraw_pending = std::mem::take(&mut *self.0.redraw_pending.borrow_mut());
+        // Windows dropped since requesting a redraw are skipped, as they have nothing left to draw
+        let redraw_events: Vec<WindowId> = {
+            let all_canvases = self.0.all_canvases.borrow();
+            redraw_pending
+                .into_iter()
+                .filter(|&id| {
+                    all_canvases
+                        .iter()
+                        .find(|&&(window_id, _)| window_id == id)
+                        .map_or(true, |(_, canvas)| canvas.strong_count() > 0)
+                })
+                .collect()
+        };
         let mut control = self.current_control_flow();
         for window_id in redraw_events {
             self.handle_event(Event::RedrawRequested(window_id), &mut control);
+            self.reveal(window_id);
         }
         self.handle_event(Event::RedrawEventsCleared, &mut control);
+
+        // The control flow may have been changed while handling the redraw events
+        self.apply_control_flow(control);
+        if self.is_closed() {
+            self.handle_loop_destroyed(&mut control);
+        }
     }
 
+    // An event loop that has exited already has been destroyed then, and `LoopDestroyed` is only
+    // emitted once
     pub fn handle_unload(&self) {
+        if self.is_closed() {
+            return;
+        }
         self.apply_control_flow(root::ControlFlow::Exit);
         let mut control = self.current_control_flow();
-        self.handle_event(Event::LoopDestroyed, &mut control);
+        self.handle_loop_destroyed(&mut control);
+    }
+
+    // Emit the final event of the event loop, and release everything it holds on to: the canvas
+    // listeners, the animation frame, the gamepad listeners and the event handler itself. This
+    // also breaks the reference cycles between the runner and the closures it feeds events to.
+    fn handle_loop_destroyed(&self, control: &mut root::ControlFlow) {
+        self.handle_event(Event::LoopDestroyed, control);
+
+        let all_canvases = std::mem::take(&mut *self.0.all_canvases.borrow_mut());
+        for (_, canvas) in all_canvases {
+            if let Some(canvas) = canvas.upgrade() {
+                // The canvas can only be borrowed if the exit was caused by one of its methods
+                // dispatching an event synchronously, in which case its listeners are removed
+                // when it is dropped instead
+                if let Ok(mut canvas) = canvas.try_borrow_mut() {
+                    canvas.remove_listeners();
+                }
+            }
+        }
+
+        self.0.events.borrow_mut().clear();
+        self.0.user_events.set(0);
+        self.0.redraw_pending.borrow_mut().clear();
+        self.0.unrevealed.borrow_mut().clear();
+        self.0.cursor_moves.borrow_mut().clear();
+        self.0.resizes.borrow_mut().clear();
+        self.0.moves.borrow_mut().clear();
+        self.0.keys.borrow_mut().clear();
+        self.0.current_key.borrow_mut().take();
+        self.0.apply_sizes.borrow_mut().clear();
+        self.stop_attention();
+        let animation_frame = self.0.request_animation_frame.borrow_mut().take();
+        let frame_timeout = self.0.frame_timeout.borrow_mut().take();
+        let gamepads = self.0.gamepads.borrow_mut().take();
+        let worker_listeners = std::mem::take(&mut *self.0.worker_listeners.borrow_mut());
+        let event_handler = match *self.0.runner.borrow_mut() {
+            Some(ref mut runner) => Some(std::mem::replace(
+                &mut runner.event_handler,
+                Box::new(|_, _| ()),
+            )),
+            None => None,
+        };
+        // Dropped outside of the borrows, as dropping the event handler can drop windows
+        drop((
+            animation_frame,
+            frame_timeout,
+            gamepads,
+            worker_listeners,
+            event_handler,
+        ));
+
+        let on_exit = self.0.on_exit.borrow_mut().take();
+        if let Some(on_exit) = on_exit {
+            on_exit();
+        }
     }
 
     // handle_event takes in events and either queues them or applies a callback
     //
     // It should only ever be called from send_event
+    // Events are dispatched synchronously when nothing else is being handled. The event handler
+    // can cause events to be sent while handling another one, e.g. by focusing a canvas, which
+    // are queued rather than handled re-entrantly, and handled right after the current event.
+    //
+    // The handler is taken out of the runner while it runs, so that the runner can still be
+    // inspected from within it, e.g. to check whether the event loop has exited.
     fn handle_event(&self, event: Event<T>, control: &mut root::ControlFlow) {
         let is_closed = self.is_closed();
 
-        match *self.0.runner.borrow_mut() {
-            Some(ref mut runner) => {
+        let event_handler = match *self.0.runner.borrow_mut() {
+            Some(ref mut runner) if !runner.is_busy => {
                 // An event is being processed, so the runner should be marked busy
                 runner.is_busy = true;
+                Some(std::mem::replace(
+                    &mut runner.event_handler,
+                    Box::new(|_: Event<T>, _: &mut root::ControlFlow| ()),
+                ))
+            }
+            // If an event is being handled without a runner somehow, or while another one is
+            // being handled, add it to the event queue so it will eventually be processed
+            _ => None,
+        };
 
-                (runner.event_handler)(event, control);
+        match event_handler {
+            Some(mut event_handler) => {
+                #[cfg(feature = "trace")]
+                trace!("Dispatching {:?}", Traced(&event));
+                self.dispatch(event, |event| event_handler(event, control));
 
                 // Maintain closed state, even if the callback changes it
                 if is_closed {
@@ -227,11 +925,17 @@ impl<T: 'static> Shared<T> {
                 }
 
                 // An event is no longer being processed
-                runner.is_busy = false;
+                if let Some(ref mut runner) = *self.0.runner.borrow_mut() {
+                    runner.event_handler = event_handler;
+                    runner.is_busy = false;
+                }
+            }
+            None => {
+                #[cfg(feature = "trace")]
+                trace!("Queued {:?} while another event is handled", Traced(&event));
+                self.0.events.borrow_mut().push_back(event);
+                return;
             }
-            // If an event is being handled without a runner somehow, add it to the event queue so
-            // it will eventually be processed
-            _ => self.0.events.borrow_mut().push_back(event),
         }
 
         // Don't take events out of the queue if the loop is closed or the runner doesn't exist
@@ -244,9 +948,128 @@ impl<T: 'static> Shared<T> {
         }
     }
 
+    // Pass an event to the handler, along with the state kept aside for it: a user event stops
+    // counting towards the bound of proxies, the `key` of the keyboard event behind a
+    // `KeyboardInput` is current while it is handled, and the size suggested by
+    // `ScaleFactorChanged` is applied once the handler returns
+    fn dispatch(&self, event: Event<T>, handler: impl FnOnce(Event<T>)) {
+        // User events pushed as synthetic events aren't counted
+        if let Event::UserEvent(_) = event {
+            let user_events = self.0.user_events.get();
+            self.0.user_events.set(user_events.saturating_sub(1));
+        }
+        self.track_key(&event);
+        let apply_size = match event {
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::ScaleFactorChanged { .. },
+            } => {
+                let apply_size = self.0.apply_sizes.borrow_mut().pop_front().flatten();
+                apply_size.map(|apply_size| (window_id, apply_size))
+            }
+            _ => None,
+        };
+        if let Some((window_id, _)) = apply_size {
+            self.0.new_inner_size.set(Some((window_id, None)));
+        }
+
+        handler(event);
+
+        if let Some((window_id, apply_size)) = apply_size {
+            let new_inner_size = self.0.new_inner_size.take().and_then(|(_, size)| size);
+            if let Some(size) = apply_size(new_inner_size) {
+                self.coalesce_resize(window_id, size);
+                self.request_redraw(window_id);
+            }
+        }
+    }
+
+    // The `key` of the keyboard event behind a `KeyboardInput` is current while it is handled
+    fn track_key(&self, event: &Event<T>) {
+        if let Event::WindowEvent {
+            event: WindowEvent::KeyboardInput { .. },
+            ..
+        } = event
+        {
+            let key = self.0.keys.borrow_mut().pop_front().flatten();
+            *self.0.current_key.borrow_mut() = key;
+        }
+    }
+
+    // Dispatch the events queued so far through the given handler, followed by the redraws
+    // requested so far, for an event loop that isn't run. Events sent while they are handled are
+    // dispatched as well, like the resizes, moves and cursor moves held back until the next
+    // animation frame. Returns the control flow the handler asked for, which is not applied,
+    // as the caller schedules the next call itself.
+    pub fn pump_events(
+        &self,
+        event_handler: &mut dyn FnMut(Event<T>, &mut root::ControlFlow),
+    ) -> root::ControlFlow {
+        let mut control = root::ControlFlow::Poll;
+        let mut handle = |event: Event<T>, control: &mut root::ControlFlow| {
+            #[cfg(feature = "trace")]
+            trace!("Pumping {:?}", Traced(&event));
+            self.dispatch(event, |event| event_handler(event, control));
+        };
+
+        let start_cause = match self.0.pumped.replace(true) {
+            false => StartCause::Init,
+            true => StartCause::Poll,
+        };
+        handle(Event::NewEvents(start_cause), &mut control);
+        loop {
+            let event = self.0.events.borrow_mut().pop_front();
+            match event {
+                Some(event) => handle(event, &mut control),
+                None => {
+                    // No animation frame sends the events held back for it, so they are due now
+                    self.send_resizes();
+                    self.send_moves();
+                    self.send_cursor_moves();
+                    if self.0.events.borrow().is_empty() {
+                        break;
+                    }
+                }
+            }
+        }
+        handle(Event::MainEventsCleared, &mut control);
+
+        let redraw_pending = std::mem::take(&mut *self.0.redraw_pending.borrow_mut());
+        for window_id in redraw_pending {
+            handle(Event::RedrawRequested(window_id), &mut control);
+            self.reveal(window_id);
+        }
+        handle(Event::RedrawEventsCleared, &mut control);
+
+        control
+    }
+
     // Apply the new ControlFlow that has been selected by the user
     // Start any necessary timeouts etc
+    //
+    // Replacing the state drops the previous timeout, which cancels it, so switching to an earlier
+    // deadline or back to `Wait` never causes a spurious wake up
     fn apply_control_flow(&self, control_flow: root::ControlFlow) {
+        #[cfg(feature = "trace")]
+        {
+            let previous = self.current_control_flow();
+            if previous != control_flow {
+                trace!(
+                    "ControlFlow changed from {:?} to {:?}",
+                    previous,
+                    control_flow
+                );
+            }
+        }
+
+        if let root::ControlFlow::WaitUntil(end) = control_flow {
+            if self.is_waiting_until(end) {
+                // The pending timeout already targets this deadline, so keep it running rather
+                // than rescheduling it on every wake up
+                return;
+            }
+        }
+
         let new_state = match control_flow {
             root::ControlFlow::Poll => {
                 let cloned = self.clone();
@@ -288,11 +1111,24 @@ impl<T: 'static> Shared<T> {
         }
     }
 
+    // Check if a timeout for the given deadline is still pending
+    fn is_waiting_until(&self, deadline: Instant) -> bool {
+        match *self.0.runner.borrow() {
+            Some(Runner {
+                state: State::WaitUntil { end, .. },
+                ..
+            }) => end == deadline && end > Instant::now(),
+            _ => false,
+        }
+    }
+
     // Check if the event loop is currently closed
-    fn is_closed(&self) -> bool {
+    pub fn is_closed(&self) -> bool {
         match *self.0.runner.borrow() {
             Some(ref runner) => runner.state.is_exit(),
-            None => false, // If the event loop is None, it has not been intialised yet, so it cannot be closed
+            // If the event loop is None, it has not been intialised yet, so it is only closed if
+            // it was dropped before running
+            None => self.0.is_abandoned.get(),
         }
     }
 
@@ -304,3 +1140,35 @@ impl<T: 'static> Shared<T> {
         }
     }
 }
+
+// Formats events for the trace log, without requiring user events to implement `Debug`
+#[cfg(feature = "trace")]
+struct Traced<'a, T: 'static>(&'a Event<T>);
+
+#[cfg(feature = "trace")]
+impl<T> fmt::Debug for Traced<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Event::NewEvents(cause) => f.debug_tuple("NewEvents").field(cause).finish(),
+            Event::WindowEvent { window_id, event } => f
+                .debug_struct("WindowEvent")
+                .field("window_id", window_id)
+                .field("event", event)
+                .finish(),
+            Event::DeviceEvent { device_id, event } => f
+                .debug_struct("DeviceEvent")
+                .field("device_id", device_id)
+                .field("event", event)
+                .finish(),
+            Event::UserEvent(_) => f.write_str("UserEvent(..)"),
+            Event::Suspended => f.write_str("Suspended"),
+            Event::Resumed => f.write_str("Resumed"),
+            Event::MainEventsCleared => f.write_str("MainEventsCleared"),
+            Event::RedrawRequested(window_id) => {
+                f.debug_tuple("RedrawRequested").field(window_id).finish()
+            }
+            Event::RedrawEventsCleared => f.write_str("RedrawEventsCleared"),
+            Event::LoopDestroyed => f.write_str("LoopDestroyed"),
+        }
+    }
+}
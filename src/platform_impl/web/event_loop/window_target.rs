@@ -1,34 +1,78 @@
-use super::{backend, device, proxy::Proxy, runner, window};
-use crate::dpi::{PhysicalSize, Size};
-use crate::event::{DeviceId, ElementState, Event, KeyboardInput, TouchPhase, WindowEvent, DeviceEvent};
+use super::{
+    backend, device,
+    gesture::{DoubleTap, Gesture, Update},
+    proxy::Proxy,
+    runner, window,
+};
+use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::event::{
+    DeviceEvent, DeviceId, ElementState, Event, Ime, KeyboardInput, ModifiersState, ScanCode,
+    Touch, TouchPhase, VirtualKeyCode, WindowEvent,
+};
 use crate::event_loop::ControlFlow;
-use crate::window::WindowId;
+use crate::platform::web::{CursorMoveCoalescing, PointerType};
+use crate::window::{Theme, WindowId};
+use std::cell::{Cell, RefCell, RefMut};
 use std::clone::Clone;
+use std::iter;
+use std::path::PathBuf;
+use std::rc::Rc;
 
 pub struct WindowTarget<T: 'static> {
     pub(crate) runner: runner::Shared<T>,
+    options: Rc<RefCell<Options>>,
 }
 
 impl<T> Clone for WindowTarget<T> {
     fn clone(&self) -> Self {
         WindowTarget {
             runner: self.runner.clone(),
+            options: self.options.clone(),
         }
     }
 }
 
+// The options set on the event loop, which apply to every window that doesn't set them itself
+#[derive(Default)]
+pub struct Options {
+    pub container: Option<backend::RawContainerType>,
+    pub passive_listeners: bool,
+    pub prevent_default: bool,
+}
+
 impl<T> WindowTarget<T> {
     pub fn new() -> Self {
         let runner = runner::Shared::new();
         let animation_frame_runner = runner.clone();
-        let animation_frame = backend::AnimationFrame::new(move |ts| animation_frame_runner.redraw());
+        let animation_frame =
+            backend::AnimationFrame::new(move |ts| animation_frame_runner.animation_frame(ts));
         runner.set_animation_frame(animation_frame);
 
+        let gamepads_runner = runner.clone();
+        let gamepads = backend::Gamepads::new(move || gamepads_runner.request_animation_frame());
+        runner.set_gamepads(gamepads);
+
         WindowTarget {
-            runner
+            runner,
+            options: Rc::new(RefCell::new(Options::default())),
         }
     }
 
+    pub fn options(&self) -> RefMut<'_, Options> {
+        self.options.borrow_mut()
+    }
+
+    // Fill in the attributes a window was built without from the options of the event loop
+    pub fn apply_options(&self, attr: &mut window::PlatformSpecificBuilderAttributes) {
+        let options = self.options.borrow();
+        if attr.canvas.is_none() && attr.container.is_none() {
+            attr.container = options.container.clone();
+        }
+        attr.passive_listeners
+            .get_or_insert(options.passive_listeners);
+        attr.prevent_default.get_or_insert(options.prevent_default);
+    }
+
     pub fn proxy(&self) -> Proxy<T> {
         Proxy::new(self.runner.clone())
     }
@@ -37,15 +81,97 @@ impl<T> WindowTarget<T> {
         self.runner.set_listener(event_handler);
     }
 
+    pub fn frame_timestamp(&self) -> Option<f64> {
+        self.runner.frame_timestamp()
+    }
+
+    pub fn current_key(&self) -> Option<String> {
+        self.runner.current_key()
+    }
+
+    // A pushed `KeyboardInput` has no keyboard event behind it, and a pushed `ScaleFactorChanged`
+    // no size to apply, so they are sent like synthetic ones
+    #[cfg(feature = "synthetic-events")]
+    pub fn push_event(&self, event: Event<T>) {
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { .. },
+                ..
+            } => self.runner.send_keyboard_inputs(iter::once((event, None))),
+            Event::WindowEvent {
+                window_id,
+                event:
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size,
+                    },
+            } => {
+                self.runner
+                    .send_scale_factor_changed(window_id, scale_factor, new_inner_size, None)
+            }
+            event => self.runner.send_event(event),
+        }
+    }
+
+    pub fn set_max_frame_rate(&self, max_frame_rate: Option<f64>) {
+        self.runner.set_max_frame_rate(max_frame_rate);
+    }
+
+    pub fn pump_events(
+        &self,
+        event_handler: &mut dyn FnMut(Event<T>, &mut ControlFlow),
+    ) -> ControlFlow {
+        self.runner.pump_events(event_handler)
+    }
+
+    pub fn set_on_exit(&self, on_exit: Box<dyn FnOnce()>) {
+        self.runner.set_on_exit(on_exit);
+    }
+
+    pub fn drop_event_loop(&self) {
+        self.runner.drop_event_loop();
+    }
+
     pub fn generate_id(&self) -> window::Id {
         window::Id(self.runner.generate_id())
     }
 
-    pub fn register(&self, canvas: &mut backend::Canvas, id: window::Id) {
+    pub fn register(&self, canvas: &Rc<RefCell<backend::Canvas>>, id: window::Id) {
+        self.runner.add_canvas(WindowId(id), canvas);
+        let canvas = &mut *canvas.borrow_mut();
+
         let runner = self.runner.clone();
         canvas.set_attribute("data-raw-handle", &id.0.to_string());
 
+        // The keys held down while the canvas is focused, as no `keyup` is received for them once
+        // the canvas loses focus
+        let pressed_keys = Rc::new(RefCell::new(
+            Vec::<(ScanCode, Option<VirtualKeyCode>)>::new(),
+        ));
+
+        let released_keys = pressed_keys.clone();
         canvas.on_blur(move || {
+            let released_keys = std::mem::take(&mut *released_keys.borrow_mut());
+            runner.send_keyboard_inputs(released_keys.into_iter().map(
+                |(scancode, virtual_keycode)| {
+                    #[allow(deprecated)]
+                    let event = Event::WindowEvent {
+                        window_id: WindowId(id),
+                        event: WindowEvent::KeyboardInput {
+                            device_id: DeviceId(unsafe { device::Id::dummy() }),
+                            input: KeyboardInput {
+                                scancode,
+                                state: ElementState::Released,
+                                virtual_keycode,
+                                modifiers: ModifiersState::empty(),
+                            },
+                            is_synthetic: true,
+                        },
+                    };
+                    (event, None)
+                },
+            ));
+            runner.set_modifiers(ModifiersState::empty());
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::Focused(false),
@@ -54,53 +180,83 @@ impl<T> WindowTarget<T> {
 
         let runner = self.runner.clone();
         canvas.on_focus(move || {
+            runner.focus_window(WindowId(id));
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::Focused(true),
             });
+            // Modifiers may have changed while the canvas wasn't focused, and browsers can't be
+            // asked for them outside of an event, so they start over and are derived again from
+            // the next keyboard or pointer event
+            runner.set_modifiers(ModifiersState::empty());
         });
 
         let runner = self.runner.clone();
-        canvas.on_keyboard_press(move |scancode, virtual_keycode, modifiers| {
+        let held_keys = pressed_keys.clone();
+        canvas.on_keyboard_press(move |scancode, virtual_keycode, modifiers, key| {
+            {
+                let mut held_keys = held_keys.borrow_mut();
+                // Repeated presses are only tracked once
+                if !held_keys.iter().any(|&(held, _)| held == scancode) {
+                    held_keys.push((scancode, virtual_keycode));
+                }
+            }
+            runner.set_modifiers(modifiers);
             #[allow(deprecated)]
-            runner.send_event(Event::WindowEvent {
+            let input = KeyboardInput {
+                scancode,
+                state: ElementState::Pressed,
+                virtual_keycode,
+                modifiers,
+            };
+            let device_id = DeviceId(unsafe { device::Id::dummy() });
+            let event = Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::KeyboardInput {
-                    device_id: DeviceId(unsafe { device::Id::dummy() }),
-                    input: KeyboardInput {
-                        scancode,
-                        state: ElementState::Pressed,
-                        virtual_keycode,
-                        modifiers,
-                    },
+                    device_id,
+                    input,
                     is_synthetic: false,
                 },
-            });
+            };
+            runner.send_keyboard_inputs(iter::once((event, Some(key))));
             runner.send_event(Event::DeviceEvent {
-                device_id: DeviceId(unsafe { device::Id::dummy() }),
-                event: DeviceEvent::ModifiersChanged(modifiers)
+                device_id,
+                event: DeviceEvent::Key(input),
             });
         });
 
         let runner = self.runner.clone();
-        canvas.on_keyboard_release(move |scancode, virtual_keycode, modifiers| {
+        canvas.on_keyboard_release(move |scancode, virtual_keycode, modifiers, key| {
+            // The virtual key code depends on the modifiers as well, so a key is released with
+            // the one it was pressed with, e.g. when Shift was released first
+            let virtual_keycode = {
+                let mut pressed_keys = pressed_keys.borrow_mut();
+                match pressed_keys.iter().position(|&(held, _)| held == scancode) {
+                    Some(index) => pressed_keys.remove(index).1,
+                    None => virtual_keycode,
+                }
+            };
+            runner.set_modifiers(modifiers);
             #[allow(deprecated)]
-            runner.send_event(Event::WindowEvent {
+            let input = KeyboardInput {
+                scancode,
+                state: ElementState::Released,
+                virtual_keycode,
+                modifiers,
+            };
+            let device_id = DeviceId(unsafe { device::Id::dummy() });
+            let event = Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::KeyboardInput {
-                    device_id: DeviceId(unsafe { device::Id::dummy() }),
-                    input: KeyboardInput {
-                        scancode,
-                        state: ElementState::Released,
-                        virtual_keycode,
-                        modifiers,
-                    },
+                    device_id,
+                    input,
                     is_synthetic: false,
                 },
-            });
+            };
+            runner.send_keyboard_inputs(iter::once((event, Some(key))));
             runner.send_event(Event::DeviceEvent {
-                device_id: DeviceId(unsafe { device::Id::dummy() }),
-                event: DeviceEvent::ModifiersChanged(modifiers)
+                device_id,
+                event: DeviceEvent::Key(input),
             });
         });
 
@@ -113,43 +269,214 @@ impl<T> WindowTarget<T> {
         });
 
         let runner = self.runner.clone();
-        canvas.on_cursor_leave(move |pointer_id| {
+        canvas.on_composition_start(move |text| {
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
-                event: WindowEvent::CursorLeft {
-                    device_id: DeviceId(device::Id(pointer_id)),
-                },
+                event: WindowEvent::Ime(Ime::Preedit {
+                    text,
+                    cursor_range: None,
+                }),
             });
         });
 
         let runner = self.runner.clone();
-        canvas.on_cursor_enter(move |pointer_id| {
+        canvas.on_composition_update(move |text| {
+            let cursor = text.len();
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
-                event: WindowEvent::CursorEntered {
-                    device_id: DeviceId(device::Id(pointer_id)),
+                event: WindowEvent::Ime(Ime::Preedit {
+                    text,
+                    cursor_range: Some((cursor, cursor)),
+                }),
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_composition_end(move |text| {
+            // The composition may have been cancelled, in which case only the preedit is cleared
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::Ime(Ime::Preedit {
+                    text: String::new(),
+                    cursor_range: None,
+                }),
+            });
+            if !text.is_empty() {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::Ime(Ime::Commit(text)),
+                });
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_inserted_text(move |text| {
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::Ime(Ime::Commit(text)),
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drag_enter(move |count| {
+            // Browsers don't expose the names of dragged files until they are dropped
+            for _ in 0..count {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::HoveredFile(PathBuf::new()),
+                });
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drag_leave(move || {
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::HoveredFileCancelled,
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drop(move |names| {
+            runner.send_events(names.into_iter().map(|name| Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::DroppedFile(PathBuf::from(name)),
+            }));
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_cursor_leave(move |pointer_id, pointer_type| {
+            runner.send_cursor_moves();
+            runner.set_cursor_position(WindowId(id), None);
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::CursorLeft {
+                    device_id: DeviceId(device::Id::pointer(id, pointer_id, pointer_type)),
                 },
             });
         });
 
+        // Outside of pointer lock, `MouseMotion` is derived from consecutive cursor positions. There
+        // is no previous position after the cursor entered the canvas, or the lock changed, in which
+        // case the movement isn't reported either while locked: browsers measure the first movement
+        // after a lock change from where they last placed the cursor, which can be far off.
+        let motion_origin = Rc::new(Cell::new(None));
+
         let runner = self.runner.clone();
-        canvas.on_cursor_move(move |pointer_id, position, modifiers| {
+        let entry_origin = motion_origin.clone();
+        canvas.on_cursor_enter(move |pointer_id, pointer_type| {
+            entry_origin.set(None);
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
-                event: WindowEvent::CursorMoved {
-                    device_id: DeviceId(device::Id(pointer_id)),
-                    position,
-                    modifiers,
+                event: WindowEvent::CursorEntered {
+                    device_id: DeviceId(device::Id::pointer(id, pointer_id, pointer_type)),
                 },
             });
         });
 
+        let coalesce_per_frame = canvas.cursor_move_coalescing() == CursorMoveCoalescing::Frame;
+
+        let runner = self.runner.clone();
+        let raw = canvas.raw().clone();
+        let position_state = canvas.locked_cursor_position();
+        let move_origin = motion_origin.clone();
+        canvas.on_cursor_move(
+            move |pointer_id, pointer_type, position, delta, modifiers| {
+                runner.set_modifiers(modifiers);
+                let is_locked = backend::is_pointer_locked(&raw);
+                let origin = move_origin.get();
+                let delta = match origin {
+                    Some(_) => delta,
+                    None => PhysicalPosition { x: 0.0, y: 0.0 },
+                };
+                let position = if is_locked {
+                    let previous = position_state.get();
+                    PhysicalPosition {
+                        x: previous.x + delta.x,
+                        y: previous.y + delta.y,
+                    }
+                } else {
+                    position
+                };
+                position_state.set(position);
+                move_origin.set(Some(position));
+                runner.set_cursor_position(WindowId(id), Some(position));
+
+                let device_id = DeviceId(device::Id::pointer(id, pointer_id, pointer_type));
+                let event = Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::CursorMoved {
+                        device_id,
+                        position,
+                        modifiers,
+                    },
+                };
+                if coalesce_per_frame {
+                    runner.coalesce_cursor_move(WindowId(id), device_id, event);
+                } else {
+                    runner.send_event(event);
+                }
+
+                let delta = if is_locked {
+                    origin.map(|_| delta)
+                } else {
+                    origin.map(|origin| PhysicalPosition {
+                        x: position.x - origin.x,
+                        y: position.y - origin.y,
+                    })
+                };
+                if let Some(delta) = delta {
+                    runner.send_event(Event::DeviceEvent {
+                        device_id,
+                        event: DeviceEvent::MouseMotion {
+                            delta: (delta.x, delta.y),
+                        },
+                    });
+                }
+            },
+        );
+
+        // The pointer lock is tracked apart from the focus of the canvas, which is reported by the
+        // focus and blur listeners. Only losing a lock that was acquired (including when the user
+        // presses Esc) is reported, as losing focus, so that applications know to show their
+        // cursor again.
+        let is_locked = Rc::new(Cell::new(false));
+
+        let runner = self.runner.clone();
+        let raw = canvas.raw().clone();
+        let locked_state = is_locked.clone();
+        canvas.on_pointer_lock_change(move || {
+            motion_origin.set(None);
+            if backend::is_pointer_locked(&raw) {
+                locked_state.set(true);
+            } else if locked_state.replace(false) {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::Focused(false),
+                });
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_pointer_lock_error(move || {
+            // The browser denied the request, possibly because it wasn't made from a user gesture
+            // or because the user just exited a previous lock. Nothing changes without a lock.
+            if is_locked.replace(false) {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::Focused(false),
+                });
+            }
+        });
+
         let runner = self.runner.clone();
-        canvas.on_mouse_press(move |pointer_id, button, modifiers| {
+        canvas.on_mouse_press(move |pointer_id, pointer_type, button, modifiers| {
+            runner.send_cursor_moves();
+            runner.set_modifiers(modifiers);
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::MouseInput {
-                    device_id: DeviceId(device::Id(pointer_id)),
+                    device_id: DeviceId(device::Id::pointer(id, pointer_id, pointer_type)),
                     state: ElementState::Pressed,
                     button,
                     modifiers,
@@ -158,11 +485,13 @@ impl<T> WindowTarget<T> {
         });
 
         let runner = self.runner.clone();
-        canvas.on_mouse_release(move |pointer_id, button, modifiers| {
+        canvas.on_mouse_release(move |pointer_id, pointer_type, button, modifiers| {
+            runner.send_cursor_moves();
+            runner.set_modifiers(modifiers);
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::MouseInput {
-                    device_id: DeviceId(device::Id(pointer_id)),
+                    device_id: DeviceId(device::Id::pointer(id, pointer_id, pointer_type)),
                     state: ElementState::Released,
                     button,
                     modifiers,
@@ -172,10 +501,12 @@ impl<T> WindowTarget<T> {
 
         let runner = self.runner.clone();
         canvas.on_mouse_wheel(move |pointer_id, delta, modifiers| {
+            runner.send_cursor_moves();
+            runner.set_modifiers(modifiers);
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::MouseWheel {
-                    device_id: DeviceId(device::Id(pointer_id)),
+                    device_id: DeviceId(device::Id::pointer(id, pointer_id, PointerType::Mouse)),
                     delta,
                     phase: TouchPhase::Moved,
                     modifiers,
@@ -183,15 +514,243 @@ impl<T> WindowTarget<T> {
             });
         });
 
+        // The scale factor the canvas was last reported with, which the distances of double taps
+        // are scaled by
+        let scale_factor = Rc::new(Cell::new(canvas.scale_factor()));
+
+        // Gestures are reported after the touch event they are derived from
+        let gesture = Rc::new(RefCell::new(Gesture::default()));
+        let double_tap = Rc::new(RefCell::new(DoubleTap::default()));
+        let gesture_events = move |touch: Option<Touch>, updates: Vec<Update>| {
+            let device_id = DeviceId(unsafe { device::Id::dummy() });
+            let updates = updates.into_iter().flat_map(move |update| {
+                iter::once(WindowEvent::TouchpadMagnify {
+                    device_id,
+                    delta: update.magnify,
+                    phase: update.phase,
+                })
+                .chain(iter::once(WindowEvent::TouchpadRotate {
+                    device_id,
+                    delta: update.rotate,
+                    phase: update.phase,
+                }))
+            });
+            touch
+                .map(WindowEvent::Touch)
+                .into_iter()
+                .chain(updates)
+                .map(move |event| Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event,
+                })
+        };
+
+        let runner = self.runner.clone();
+        let start_gesture = gesture.clone();
+        let start_double_tap = double_tap.clone();
+        canvas.on_touch_start(move |touch_id, location, force, radius| {
+            start_double_tap
+                .borrow_mut()
+                .touch_start(touch_id, location);
+            let updates = start_gesture.borrow_mut().touch_start(touch_id, location);
+            runner.send_events(gesture_events(
+                Some(Touch {
+                    device_id: DeviceId(device::Id::touch(id)),
+                    phase: TouchPhase::Started,
+                    location,
+                    force: Some(force),
+                    radius,
+                    id: touch_id as u64,
+                }),
+                updates,
+            ));
+        });
+
+        let runner = self.runner.clone();
+        let move_gesture = gesture.clone();
+        let move_double_tap = double_tap.clone();
+        let current_scale_factor = scale_factor.clone();
+        canvas.on_touch_move(move |touch_id, location, force, radius| {
+            move_double_tap
+                .borrow_mut()
+                .touch_move(touch_id, location, current_scale_factor.get());
+            let updates = move_gesture.borrow_mut().touch_move(touch_id, location);
+            runner.send_events(gesture_events(
+                Some(Touch {
+                    device_id: DeviceId(device::Id::touch(id)),
+                    phase: TouchPhase::Moved,
+                    location,
+                    force: Some(force),
+                    radius,
+                    id: touch_id as u64,
+                }),
+                updates,
+            ));
+        });
+
+        let runner = self.runner.clone();
+        let end_gesture = gesture.clone();
+        let end_double_tap = double_tap.clone();
+        let current_scale_factor = scale_factor.clone();
+        canvas.on_touch_end(move |touch_id, location, force, radius| {
+            let updates = end_gesture
+                .borrow_mut()
+                .touch_end(touch_id, TouchPhase::Ended);
+            let is_double_tap = end_double_tap.borrow_mut().touch_end(
+                touch_id,
+                location,
+                TouchPhase::Ended,
+                current_scale_factor.get(),
+            );
+            runner.send_events(gesture_events(
+                Some(Touch {
+                    device_id: DeviceId(device::Id::touch(id)),
+                    phase: TouchPhase::Ended,
+                    location,
+                    force: Some(force),
+                    radius,
+                    id: touch_id as u64,
+                }),
+                updates,
+            ));
+            if is_double_tap {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::SmartMagnify {
+                        device_id: DeviceId(device::Id::touch(id)),
+                    },
+                });
+            }
+        });
+
+        let runner = self.runner.clone();
+        let cancel_gesture = gesture.clone();
+        let cancel_double_tap = double_tap.clone();
+        let current_scale_factor = scale_factor.clone();
+        canvas.on_touch_cancel(move |touch_id, location, force, radius| {
+            let updates = cancel_gesture
+                .borrow_mut()
+                .touch_end(touch_id, TouchPhase::Cancelled);
+            cancel_double_tap.borrow_mut().touch_end(
+                touch_id,
+                location,
+                TouchPhase::Cancelled,
+                current_scale_factor.get(),
+            );
+            runner.send_events(gesture_events(
+                Some(Touch {
+                    device_id: DeviceId(device::Id::touch(id)),
+                    phase: TouchPhase::Cancelled,
+                    location,
+                    force: Some(force),
+                    radius,
+                    id: touch_id as u64,
+                }),
+                updates,
+            ));
+        });
+
+        // Besides the touch, the pressure and tilt of the pen are reported as axes, each only when
+        // it changed since the last event of the contact
+        let runner = self.runner.clone();
+        let mut pen_axes: Vec<(i32, [f64; 3])> = Vec::new();
+        canvas.on_pen(move |pointer_id, phase, location, force, axes| {
+            let device_id = DeviceId(device::Id::pointer(id, pointer_id, PointerType::Pen));
+            let previous = match pen_axes.iter_mut().find(|(pen, _)| *pen == pointer_id) {
+                Some((_, previous)) => std::mem::replace(previous, axes),
+                None => {
+                    pen_axes.push((pointer_id, axes));
+                    [f64::NAN; 3]
+                }
+            };
+            if let TouchPhase::Ended | TouchPhase::Cancelled = phase {
+                pen_axes.retain(|(pen, _)| *pen != pointer_id);
+            }
+
+            runner.send_cursor_moves();
+            let touch = Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::Touch(Touch {
+                    device_id,
+                    phase,
+                    location,
+                    force: Some(force),
+                    radius: None,
+                    id: pointer_id as u64,
+                }),
+            };
+            #[allow(clippy::float_cmp)]
+            let axis_motions = axes
+                .iter()
+                .zip(previous.iter())
+                .enumerate()
+                .filter(|(_, (value, last))| value != last)
+                .map(|(axis, (&value, _))| Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::AxisMotion {
+                        device_id,
+                        axis: axis as u32,
+                        value,
+                    },
+                })
+                .collect::<Vec<_>>();
+            runner.send_events(iter::once(touch).chain(axis_motions));
+        });
+
+        // Safari reports gestures of the trackpad, which produce no touch events, as gesture
+        // events with the scale and rotation since their start. It reports gestures on touch
+        // screens as well, which are already derived from the touch events.
+        let runner = self.runner.clone();
+        let mut safari_gesture = None;
+        canvas.on_gesture(move |phase, scale, rotation| {
+            let update = match (phase, safari_gesture) {
+                (TouchPhase::Started, _) if !gesture.borrow().is_active() => {
+                    safari_gesture = Some((scale, rotation));
+                    Update::new(phase)
+                }
+                (TouchPhase::Moved, Some((previous_scale, previous_rotation))) => {
+                    safari_gesture = Some((scale, rotation));
+                    Update {
+                        phase,
+                        magnify: if previous_scale > 0.0 {
+                            scale / previous_scale - 1.0
+                        } else {
+                            0.0
+                        },
+                        // Safari measures the rotation clockwise
+                        rotate: previous_rotation - rotation,
+                    }
+                }
+                (TouchPhase::Ended, Some(_)) => {
+                    safari_gesture = None;
+                    Update::new(phase)
+                }
+                _ => return,
+            };
+            runner.send_events(gesture_events(None, vec![update]));
+        });
+
         let runner = self.runner.clone();
         let raw = canvas.raw().clone();
+        let size_constraints = canvas.size_constraints();
+        let auto_size = canvas.auto_size();
 
         // The size to restore to after exiting fullscreen.
         let mut intended_size = PhysicalSize {
             width: raw.width() as u32,
             height: raw.height() as u32,
         };
-        canvas.on_fullscreen_change(move || {
+        canvas.on_fullscreen_change(move |scale_factor| {
+            // Keys can only be locked in fullscreen
+            if !backend::is_fullscreen(&raw) {
+                backend::unlock_keyboard();
+            }
+
+            // Without automatic sizing, the new displayed size is only reported once it is laid out
+            if !auto_size.get() {
+                return;
+            }
+
             // If the canvas is marked as fullscreen, it is moving *into* fullscreen
             // If it is not, it is moving *out of* fullscreen
             let new_size = if backend::is_fullscreen(&raw) {
@@ -200,32 +759,227 @@ impl<T> WindowTarget<T> {
                     height: raw.height() as u32,
                 };
 
-                backend::window_size().to_physical(backend::scale_factor())
+                backend::window_size().to_physical(scale_factor)
             } else {
-                intended_size
+                size_constraints.clamp(intended_size, scale_factor)
             };
 
-            backend::set_canvas_size(&raw, Size::Physical(new_size));
+            backend::set_canvas_size(&raw, Size::Physical(new_size), scale_factor);
+            runner.coalesce_resize(WindowId(id), new_size);
+            runner.request_redraw(WindowId(id));
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_scroll(move || runner.coalesce_move(WindowId(id)));
+
+        // Changes of the device pixel ratio don't always resize the canvas, so they are detected
+        // through window resizes as well
+        let runner = self.runner.clone();
+        let raw = canvas.raw().clone();
+        let current_scale_factor = scale_factor.clone();
+        let auto_size = canvas.auto_size();
+        canvas.on_resize(move |new_scale_factor| {
+            // Resizing the browser window changes the layout of the page
+            runner.coalesce_move(WindowId(id));
+
+            let old_scale_factor = current_scale_factor.replace(new_scale_factor);
+            #[allow(clippy::float_cmp)]
+            let changed = old_scale_factor != new_scale_factor;
+            if changed {
+                // The displayed size is unchanged, so the drawing buffer keeps its logical size
+                let new_size = backend::canvas_size(&raw)
+                    .to_logical::<f64>(old_scale_factor)
+                    .to_physical(new_scale_factor);
+                let auto_size = auto_size.clone();
+                let apply = move |raw: &backend::RawCanvasType, size: PhysicalSize<u32>| {
+                    if auto_size.get() {
+                        raw.set_width(size.width);
+                        raw.set_height(size.height);
+                    }
+                };
+                send_scale_factor_changed(&runner, id, &raw, new_scale_factor, new_size, apply);
+            }
+        });
+
+        let runner = self.runner.clone();
+        let raw = canvas.raw().clone();
+        let current_scale_factor = scale_factor.clone();
+        let auto_size = canvas.auto_size();
+        let mut displayed_size = None;
+        canvas.on_size_change(move |new_size, new_scale_factor| {
+            // Layouts that center the canvas move it along with its size
+            runner.coalesce_move(WindowId(id));
+
+            let old_displayed_size = displayed_size.replace(new_size);
+
+            #[allow(clippy::float_cmp)]
+            let scale_factor_changed =
+                current_scale_factor.replace(new_scale_factor) != new_scale_factor;
+            if scale_factor_changed {
+                let auto_size = auto_size.clone();
+                let apply = move |raw: &backend::RawCanvasType, size: PhysicalSize<u32>| {
+                    if auto_size.get() {
+                        raw.set_width(size.width);
+                        raw.set_height(size.height);
+                    }
+                };
+                send_scale_factor_changed(&runner, id, &raw, new_scale_factor, new_size, apply);
+                return;
+            }
+
+            // Without automatic sizing, the displayed size is reported for the application to
+            // resize the drawing buffer itself
+            if !auto_size.get() {
+                if old_displayed_size != Some(new_size) {
+                    runner.coalesce_resize(WindowId(id), new_size);
+                    runner.request_redraw(WindowId(id));
+                }
+                return;
+            }
+
+            // Keep the drawing buffer matched to the displayed size, so the inner size always
+            // reflects the layout of the page
+            if backend::canvas_size(&raw) != new_size {
+                raw.set_width(new_size.width);
+                raw.set_height(new_size.height);
+                runner.coalesce_resize(WindowId(id), new_size);
+                runner.request_redraw(WindowId(id));
+            }
+        });
+
+        // The drawing buffer is resized to keep the logical size of the canvas when the scale
+        // factor is overridden
+        let runner = self.runner.clone();
+        let raw = canvas.raw().clone();
+        let auto_size = canvas.auto_size();
+        canvas.on_scale_factor_override(move |new_scale_factor| {
+            let old_scale_factor = scale_factor.replace(new_scale_factor);
+            #[allow(clippy::float_cmp)]
+            let changed = old_scale_factor != new_scale_factor;
+            if !changed {
+                return;
+            }
+
+            let new_size = backend::canvas_size(&raw)
+                .to_logical::<f64>(old_scale_factor)
+                .to_physical(new_scale_factor);
+            let auto_size = auto_size.clone();
+            let apply = move |raw: &backend::RawCanvasType, size: PhysicalSize<u32>| {
+                if auto_size.get() {
+                    backend::set_canvas_size(raw, Size::Physical(size), new_scale_factor)
+                }
+            };
+            send_scale_factor_changed(&runner, id, &raw, new_scale_factor, new_size, apply);
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_dark_mode(move |is_dark_mode| {
+            let theme = if is_dark_mode {
+                Theme::Dark
+            } else {
+                Theme::Light
+            };
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
-                event: WindowEvent::Resized(new_size),
+                event: WindowEvent::ThemeChanged(theme),
+            });
+        });
+
+        let runner = self.runner.clone();
+        let is_visible = canvas.is_visible();
+        canvas.on_visibility_change(move |is_hidden| {
+            runner.set_page_hidden(is_hidden);
+            // A hidden canvas stays occluded either way
+            if is_visible.get() {
+                runner.send_event(Event::WindowEvent {
+                    window_id: WindowId(id),
+                    event: WindowEvent::Occluded(is_hidden),
+                });
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_context_lost(move || {
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::ContextLost,
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_context_restored(move || {
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::ContextRestored,
             });
-            runner.request_redraw(WindowId(id));
         });
 
+        canvas.on_context_menu();
+
         let runner = self.runner.clone();
-        canvas.on_resize(move || {
-            let scale_factor = backend::scale_factor();
-            let new_inner_size = backend::window_size().to_physical(scale_factor);
+        canvas.on_orientation_change(move |orientation| {
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
-                event: WindowEvent::ScaleFactorChanged{ scale_factor, new_inner_size }
+                event: WindowEvent::OrientationChanged(orientation),
             });
         });
 
         let runner = self.runner.clone();
         canvas.on_before_unload(move || {
-            runner.handle_unload();
+            runner.send_event(Event::WindowEvent {
+                window_id: WindowId(id),
+                event: WindowEvent::CloseRequested,
+            });
+        });
+
+        // A page kept in the back/forward cache may be shown again, so the event loop is only
+        // suspended then
+        let runner = self.runner.clone();
+        canvas.on_page_hide(move |persisted| {
+            if persisted {
+                runner.suspend();
+            } else {
+                runner.handle_unload();
+            }
         });
+
+        let runner = self.runner.clone();
+        canvas.on_page_show(move || runner.resume());
+
+        let runner = self.runner.clone();
+        canvas.on_freeze(move || runner.suspend());
+
+        let runner = self.runner.clone();
+        canvas.on_resume(move || runner.resume());
     }
 }
+
+// The suggested size is applied once `ScaleFactorChanged` has been handled, unless the handler
+// set another one with `Window::set_inner_size`, which is applied like `set_inner_size` would
+fn send_scale_factor_changed<T>(
+    runner: &runner::Shared<T>,
+    id: window::Id,
+    raw: &backend::RawCanvasType,
+    scale_factor: f64,
+    new_inner_size: PhysicalSize<u32>,
+    apply_size: impl FnOnce(&backend::RawCanvasType, PhysicalSize<u32>) + 'static,
+) {
+    // Resizes still held back happened before the change of the scale factor
+    runner.send_resizes();
+
+    let raw = raw.clone();
+    let apply_size: runner::ApplySize = Box::new(move |size| {
+        let old_size = backend::canvas_size(&raw);
+        match size {
+            Some(size) => backend::set_canvas_size(&raw, Size::Physical(size), scale_factor),
+            None => apply_size(&raw, new_inner_size),
+        }
+        let size = backend::canvas_size(&raw);
+        if size != old_size {
+            Some(size)
+        } else {
+            None
+        }
+    });
+    runner.send_scale_factor_changed(WindowId(id), scale_factor, new_inner_size, Some(apply_size));
+}
@@ -0,0 +1,1018 @@
+use crate::event::{ScanCode, VirtualKeyCode};
+
+// The `KeyboardEvent.location` of a key, which tells apart the keys that exist more than once
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyLocation {
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+// The scancode identifies the physical key through `KeyboardEvent.code`, which doesn't depend on
+// the keyboard layout, unlike the deprecated `keyCode`. The values are those of the Linux evdev
+// interface, which the X11 and Wayland backends report as well. Keys without a code are all
+// reported as 0.
+pub fn scan_code(code: &str, key: &str, location: KeyLocation) -> ScanCode {
+    code_scan_code(code)
+        .or_else(|| located_code(key, location).and_then(code_scan_code))
+        .unwrap_or(0)
+}
+
+// Some virtual keyboards and older browsers leave `code` empty, but still tell the left and right
+// modifiers, and the numpad, apart through `location`. The code is derived from the key then.
+fn located_code(key: &str, location: KeyLocation) -> Option<&'static str> {
+    Some(match (location, key) {
+        (KeyLocation::Left, "Shift") => "ShiftLeft",
+        (KeyLocation::Left, "Control") => "ControlLeft",
+        (KeyLocation::Left, "Alt") => "AltLeft",
+        (KeyLocation::Left, "Meta") | (KeyLocation::Left, "OS") => "MetaLeft",
+        (KeyLocation::Right, "Shift") => "ShiftRight",
+        (KeyLocation::Right, "Control") => "ControlRight",
+        (KeyLocation::Right, "Alt") | (KeyLocation::Right, "AltGraph") => "AltRight",
+        (KeyLocation::Right, "Meta") | (KeyLocation::Right, "OS") => "MetaRight",
+        // Without Num Lock, the digits of the numpad produce the keys printed below them
+        (KeyLocation::Numpad, key) => match key {
+            "0" | "Insert" => "Numpad0",
+            "1" | "End" => "Numpad1",
+            "2" | "ArrowDown" => "Numpad2",
+            "3" | "PageDown" => "Numpad3",
+            "4" | "ArrowLeft" => "Numpad4",
+            "5" | "Clear" => "Numpad5",
+            "6" | "ArrowRight" => "Numpad6",
+            "7" | "Home" => "Numpad7",
+            "8" | "ArrowUp" => "Numpad8",
+            "9" | "PageUp" => "Numpad9",
+            "." | "," | "Delete" | "Decimal" => "NumpadDecimal",
+            "+" | "Add" => "NumpadAdd",
+            "-" | "Subtract" => "NumpadSubtract",
+            "*" | "Multiply" => "NumpadMultiply",
+            "/" | "Divide" => "NumpadDivide",
+            "=" => "NumpadEqual",
+            "Enter" => "NumpadEnter",
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+fn code_scan_code(code: &str) -> Option<ScanCode> {
+    Some(match code {
+        "Escape" => 1,
+        "Digit1" => 2,
+        "Digit2" => 3,
+        "Digit3" => 4,
+        "Digit4" => 5,
+        "Digit5" => 6,
+        "Digit6" => 7,
+        "Digit7" => 8,
+        "Digit8" => 9,
+        "Digit9" => 10,
+        "Digit0" => 11,
+        "Minus" => 12,
+        "Equal" => 13,
+        "Backspace" => 14,
+        "Tab" => 15,
+        "KeyQ" => 16,
+        "KeyW" => 17,
+        "KeyE" => 18,
+        "KeyR" => 19,
+        "KeyT" => 20,
+        "KeyY" => 21,
+        "KeyU" => 22,
+        "KeyI" => 23,
+        "KeyO" => 24,
+        "KeyP" => 25,
+        "BracketLeft" => 26,
+        "BracketRight" => 27,
+        "Enter" => 28,
+        "ControlLeft" => 29,
+        "KeyA" => 30,
+        "KeyS" => 31,
+        "KeyD" => 32,
+        "KeyF" => 33,
+        "KeyG" => 34,
+        "KeyH" => 35,
+        "KeyJ" => 36,
+        "KeyK" => 37,
+        "KeyL" => 38,
+        "Semicolon" => 39,
+        "Quote" => 40,
+        "Backquote" => 41,
+        "ShiftLeft" => 42,
+        "Backslash" => 43,
+        "KeyZ" => 44,
+        "KeyX" => 45,
+        "KeyC" => 46,
+        "KeyV" => 47,
+        "KeyB" => 48,
+        "KeyN" => 49,
+        "KeyM" => 50,
+        "Comma" => 51,
+        "Period" => 52,
+        "Slash" => 53,
+        "ShiftRight" => 54,
+        "NumpadMultiply" => 55,
+        "AltLeft" => 56,
+        "Space" => 57,
+        "CapsLock" => 58,
+        "F1" => 59,
+        "F2" => 60,
+        "F3" => 61,
+        "F4" => 62,
+        "F5" => 63,
+        "F6" => 64,
+        "F7" => 65,
+        "F8" => 66,
+        "F9" => 67,
+        "F10" => 68,
+        "NumLock" => 69,
+        "ScrollLock" => 70,
+        "Numpad7" => 71,
+        "Numpad8" => 72,
+        "Numpad9" => 73,
+        "NumpadSubtract" => 74,
+        "Numpad4" => 75,
+        "Numpad5" => 76,
+        "Numpad6" => 77,
+        "NumpadAdd" => 78,
+        "Numpad1" => 79,
+        "Numpad2" => 80,
+        "Numpad3" => 81,
+        "Numpad0" => 82,
+        "NumpadDecimal" => 83,
+        "IntlBackslash" => 86,
+        "F11" => 87,
+        "F12" => 88,
+        "IntlRo" => 89,
+        "Lang3" => 90,
+        "Lang4" => 91,
+        "Convert" => 92,
+        "KanaMode" => 93,
+        "NonConvert" => 94,
+        "NumpadEnter" => 96,
+        "ControlRight" => 97,
+        "NumpadDivide" => 98,
+        "PrintScreen" => 99,
+        "AltRight" => 100,
+        "Home" => 102,
+        "ArrowUp" => 103,
+        "PageUp" => 104,
+        "ArrowLeft" => 105,
+        "ArrowRight" => 106,
+        "End" => 107,
+        "ArrowDown" => 108,
+        "PageDown" => 109,
+        "Insert" => 110,
+        "Delete" => 111,
+        // Firefox used the names of the `key` values for the volume keys
+        "AudioVolumeMute" | "VolumeMute" => 113,
+        "AudioVolumeDown" | "VolumeDown" => 114,
+        "AudioVolumeUp" | "VolumeUp" => 115,
+        "Power" => 116,
+        "NumpadEqual" => 117,
+        "Pause" => 119,
+        "NumpadComma" => 121,
+        "Lang1" => 122,
+        "Lang2" => 123,
+        "IntlYen" => 124,
+        // Older versions of Firefox call the meta keys "OS"
+        "MetaLeft" | "OSLeft" => 125,
+        "MetaRight" | "OSRight" => 126,
+        "ContextMenu" => 127,
+        "BrowserStop" => 128,
+        "Again" => 129,
+        "Props" => 130,
+        "Undo" => 131,
+        "Copy" => 133,
+        "Open" => 134,
+        "Paste" => 135,
+        "Find" => 136,
+        "Cut" => 137,
+        "Help" => 138,
+        "LaunchApp2" => 140,
+        "Sleep" => 142,
+        "WakeUp" => 143,
+        "LaunchApp1" => 144,
+        "LaunchMail" => 155,
+        "BrowserFavorites" => 156,
+        "BrowserBack" => 158,
+        "BrowserForward" => 159,
+        "Eject" => 161,
+        "MediaTrackNext" => 163,
+        "MediaPlayPause" => 164,
+        "MediaTrackPrevious" => 165,
+        "MediaStop" => 166,
+        "MediaSelect" => 171,
+        "BrowserHome" => 172,
+        "BrowserRefresh" => 173,
+        "NumpadParenLeft" => 179,
+        "NumpadParenRight" => 180,
+        "F13" => 183,
+        "F14" => 184,
+        "F15" => 185,
+        "F16" => 186,
+        "F17" => 187,
+        "F18" => 188,
+        "F19" => 189,
+        "F20" => 190,
+        "F21" => 191,
+        "F22" => 192,
+        "F23" => 193,
+        "F24" => 194,
+        "BrowserSearch" => 217,
+        "Fn" => 464,
+        _ => return None,
+    })
+}
+
+// Like on other platforms, the virtual key code depends on the keyboard layout, so it is derived
+// from `KeyboardEvent.key` where possible. Modifier and numpad keys are told apart by their code,
+// as are keys producing characters without a virtual key code, e.g. dead keys, or digits that
+// need Shift on some layouts.
+pub fn virtual_key_code(code: &str, key: &str, location: KeyLocation) -> Option<VirtualKeyCode> {
+    if location != KeyLocation::Standard {
+        return code_virtual_key_code(code)
+            .or_else(|| located_code(key, location).and_then(code_virtual_key_code));
+    }
+
+    key_virtual_key_code(key).or_else(|| code_virtual_key_code(code))
+}
+
+fn key_virtual_key_code(key: &str) -> Option<VirtualKeyCode> {
+    let mut chars = key.chars();
+    if let (Some(character), None) = (chars.next(), chars.next()) {
+        return character_virtual_key_code(character);
+    }
+
+    Some(match key {
+        "Escape" | "Esc" => VirtualKeyCode::Escape,
+        "F1" => VirtualKeyCode::F1,
+        "F2" => VirtualKeyCode::F2,
+        "F3" => VirtualKeyCode::F3,
+        "F4" => VirtualKeyCode::F4,
+        "F5" => VirtualKeyCode::F5,
+        "F6" => VirtualKeyCode::F6,
+        "F7" => VirtualKeyCode::F7,
+        "F8" => VirtualKeyCode::F8,
+        "F9" => VirtualKeyCode::F9,
+        "F10" => VirtualKeyCode::F10,
+        "F11" => VirtualKeyCode::F11,
+        "F12" => VirtualKeyCode::F12,
+        "F13" => VirtualKeyCode::F13,
+        "F14" => VirtualKeyCode::F14,
+        "F15" => VirtualKeyCode::F15,
+        "F16" => VirtualKeyCode::F16,
+        "F17" => VirtualKeyCode::F17,
+        "F18" => VirtualKeyCode::F18,
+        "F19" => VirtualKeyCode::F19,
+        "F20" => VirtualKeyCode::F20,
+        "F21" => VirtualKeyCode::F21,
+        "F22" => VirtualKeyCode::F22,
+        "F23" => VirtualKeyCode::F23,
+        "F24" => VirtualKeyCode::F24,
+        "PrintScreen" => VirtualKeyCode::Snapshot,
+        "ScrollLock" | "Scroll" => VirtualKeyCode::Scroll,
+        "Pause" => VirtualKeyCode::Pause,
+        "Insert" => VirtualKeyCode::Insert,
+        "Home" => VirtualKeyCode::Home,
+        "Delete" | "Del" => VirtualKeyCode::Delete,
+        "End" => VirtualKeyCode::End,
+        "PageDown" => VirtualKeyCode::PageDown,
+        "PageUp" => VirtualKeyCode::PageUp,
+        // Older browsers report the arrow keys without the "Arrow" prefix
+        "ArrowLeft" | "Left" => VirtualKeyCode::Left,
+        "ArrowUp" | "Up" => VirtualKeyCode::Up,
+        "ArrowRight" | "Right" => VirtualKeyCode::Right,
+        "ArrowDown" | "Down" => VirtualKeyCode::Down,
+        "Backspace" => VirtualKeyCode::Back,
+        "Enter" => VirtualKeyCode::Return,
+        "Tab" => VirtualKeyCode::Tab,
+        "Compose" => VirtualKeyCode::Compose,
+        "CapsLock" => VirtualKeyCode::Capital,
+        "ContextMenu" | "Apps" => VirtualKeyCode::Apps,
+        "Convert" => VirtualKeyCode::Convert,
+        "NonConvert" => VirtualKeyCode::NoConvert,
+        "KanaMode" => VirtualKeyCode::Kana,
+        "KanjiMode" => VirtualKeyCode::Kanji,
+        "AudioVolumeMute" | "VolumeMute" => VirtualKeyCode::Mute,
+        "AudioVolumeDown" | "VolumeDown" => VirtualKeyCode::VolumeDown,
+        "AudioVolumeUp" | "VolumeUp" => VirtualKeyCode::VolumeUp,
+        "MediaTrackNext" | "MediaNextTrack" => VirtualKeyCode::NextTrack,
+        "MediaTrackPrevious" | "MediaPreviousTrack" => VirtualKeyCode::PrevTrack,
+        "MediaPlayPause" => VirtualKeyCode::PlayPause,
+        "MediaStop" => VirtualKeyCode::MediaStop,
+        "BrowserBack" => VirtualKeyCode::WebBack,
+        "BrowserFavorites" => VirtualKeyCode::WebFavorites,
+        "BrowserForward" => VirtualKeyCode::WebForward,
+        "BrowserHome" => VirtualKeyCode::WebHome,
+        "BrowserRefresh" => VirtualKeyCode::WebRefresh,
+        "BrowserSearch" => VirtualKeyCode::WebSearch,
+        "BrowserStop" => VirtualKeyCode::WebStop,
+        "LaunchMail" => VirtualKeyCode::Mail,
+        "LaunchApplication1" | "LaunchMyComputer" => VirtualKeyCode::MyComputer,
+        "LaunchApplication2" | "LaunchCalculator" => VirtualKeyCode::Calculator,
+        "Power" => VirtualKeyCode::Power,
+        "Standby" => VirtualKeyCode::Sleep,
+        "WakeUp" => VirtualKeyCode::Wake,
+        "Copy" => VirtualKeyCode::Copy,
+        "Paste" => VirtualKeyCode::Paste,
+        "Cut" => VirtualKeyCode::Cut,
+        _ => return None,
+    })
+}
+
+fn character_virtual_key_code(character: char) -> Option<VirtualKeyCode> {
+    Some(match character.to_ascii_lowercase() {
+        '1' => VirtualKeyCode::Key1,
+        '2' => VirtualKeyCode::Key2,
+        '3' => VirtualKeyCode::Key3,
+        '4' => VirtualKeyCode::Key4,
+        '5' => VirtualKeyCode::Key5,
+        '6' => VirtualKeyCode::Key6,
+        '7' => VirtualKeyCode::Key7,
+        '8' => VirtualKeyCode::Key8,
+        '9' => VirtualKeyCode::Key9,
+        '0' => VirtualKeyCode::Key0,
+        'a' => VirtualKeyCode::A,
+        'b' => VirtualKeyCode::B,
+        'c' => VirtualKeyCode::C,
+        'd' => VirtualKeyCode::D,
+        'e' => VirtualKeyCode::E,
+        'f' => VirtualKeyCode::F,
+        'g' => VirtualKeyCode::G,
+        'h' => VirtualKeyCode::H,
+        'i' => VirtualKeyCode::I,
+        'j' => VirtualKeyCode::J,
+        'k' => VirtualKeyCode::K,
+        'l' => VirtualKeyCode::L,
+        'm' => VirtualKeyCode::M,
+        'n' => VirtualKeyCode::N,
+        'o' => VirtualKeyCode::O,
+        'p' => VirtualKeyCode::P,
+        'q' => VirtualKeyCode::Q,
+        'r' => VirtualKeyCode::R,
+        's' => VirtualKeyCode::S,
+        't' => VirtualKeyCode::T,
+        'u' => VirtualKeyCode::U,
+        'v' => VirtualKeyCode::V,
+        'w' => VirtualKeyCode::W,
+        'x' => VirtualKeyCode::X,
+        'y' => VirtualKeyCode::Y,
+        'z' => VirtualKeyCode::Z,
+        ' ' => VirtualKeyCode::Space,
+        '\'' => VirtualKeyCode::Apostrophe,
+        '@' => VirtualKeyCode::At,
+        '\\' => VirtualKeyCode::Backslash,
+        '^' => VirtualKeyCode::Caret,
+        ':' => VirtualKeyCode::Colon,
+        ',' => VirtualKeyCode::Comma,
+        '=' => VirtualKeyCode::Equals,
+        '`' => VirtualKeyCode::Grave,
+        '[' => VirtualKeyCode::LBracket,
+        '-' => VirtualKeyCode::Minus,
+        '.' => VirtualKeyCode::Period,
+        ']' => VirtualKeyCode::RBracket,
+        ';' => VirtualKeyCode::Semicolon,
+        '/' => VirtualKeyCode::Slash,
+        '_' => VirtualKeyCode::Underline,
+        '¥' => VirtualKeyCode::Yen,
+        _ => return None,
+    })
+}
+
+// The virtual key code of the key at the given position on a US keyboard
+fn code_virtual_key_code(code: &str) -> Option<VirtualKeyCode> {
+    Some(match code {
+        "Digit1" => VirtualKeyCode::Key1,
+        "Digit2" => VirtualKeyCode::Key2,
+        "Digit3" => VirtualKeyCode::Key3,
+        "Digit4" => VirtualKeyCode::Key4,
+        "Digit5" => VirtualKeyCode::Key5,
+        "Digit6" => VirtualKeyCode::Key6,
+        "Digit7" => VirtualKeyCode::Key7,
+        "Digit8" => VirtualKeyCode::Key8,
+        "Digit9" => VirtualKeyCode::Key9,
+        "Digit0" => VirtualKeyCode::Key0,
+        "KeyA" => VirtualKeyCode::A,
+        "KeyB" => VirtualKeyCode::B,
+        "KeyC" => VirtualKeyCode::C,
+        "KeyD" => VirtualKeyCode::D,
+        "KeyE" => VirtualKeyCode::E,
+        "KeyF" => VirtualKeyCode::F,
+        "KeyG" => VirtualKeyCode::G,
+        "KeyH" => VirtualKeyCode::H,
+        "KeyI" => VirtualKeyCode::I,
+        "KeyJ" => VirtualKeyCode::J,
+        "KeyK" => VirtualKeyCode::K,
+        "KeyL" => VirtualKeyCode::L,
+        "KeyM" => VirtualKeyCode::M,
+        "KeyN" => VirtualKeyCode::N,
+        "KeyO" => VirtualKeyCode::O,
+        "KeyP" => VirtualKeyCode::P,
+        "KeyQ" => VirtualKeyCode::Q,
+        "KeyR" => VirtualKeyCode::R,
+        "KeyS" => VirtualKeyCode::S,
+        "KeyT" => VirtualKeyCode::T,
+        "KeyU" => VirtualKeyCode::U,
+        "KeyV" => VirtualKeyCode::V,
+        "KeyW" => VirtualKeyCode::W,
+        "KeyX" => VirtualKeyCode::X,
+        "KeyY" => VirtualKeyCode::Y,
+        "KeyZ" => VirtualKeyCode::Z,
+        "Escape" => VirtualKeyCode::Escape,
+        "F1" => VirtualKeyCode::F1,
+        "F2" => VirtualKeyCode::F2,
+        "F3" => VirtualKeyCode::F3,
+        "F4" => VirtualKeyCode::F4,
+        "F5" => VirtualKeyCode::F5,
+        "F6" => VirtualKeyCode::F6,
+        "F7" => VirtualKeyCode::F7,
+        "F8" => VirtualKeyCode::F8,
+        "F9" => VirtualKeyCode::F9,
+        "F10" => VirtualKeyCode::F10,
+        "F11" => VirtualKeyCode::F11,
+        "F12" => VirtualKeyCode::F12,
+        "F13" => VirtualKeyCode::F13,
+        "F14" => VirtualKeyCode::F14,
+        "F15" => VirtualKeyCode::F15,
+        "F16" => VirtualKeyCode::F16,
+        "F17" => VirtualKeyCode::F17,
+        "F18" => VirtualKeyCode::F18,
+        "F19" => VirtualKeyCode::F19,
+        "F20" => VirtualKeyCode::F20,
+        "F21" => VirtualKeyCode::F21,
+        "F22" => VirtualKeyCode::F22,
+        "F23" => VirtualKeyCode::F23,
+        "F24" => VirtualKeyCode::F24,
+        "PrintScreen" => VirtualKeyCode::Snapshot,
+        "ScrollLock" => VirtualKeyCode::Scroll,
+        "Pause" => VirtualKeyCode::Pause,
+        "Insert" => VirtualKeyCode::Insert,
+        "Home" => VirtualKeyCode::Home,
+        "Delete" => VirtualKeyCode::Delete,
+        "End" => VirtualKeyCode::End,
+        "PageDown" => VirtualKeyCode::PageDown,
+        "PageUp" => VirtualKeyCode::PageUp,
+        "ArrowLeft" => VirtualKeyCode::Left,
+        "ArrowUp" => VirtualKeyCode::Up,
+        "ArrowRight" => VirtualKeyCode::Right,
+        "ArrowDown" => VirtualKeyCode::Down,
+        "Backspace" => VirtualKeyCode::Back,
+        "Enter" => VirtualKeyCode::Return,
+        "Space" => VirtualKeyCode::Space,
+        "Compose" => VirtualKeyCode::Compose,
+        "Caret" => VirtualKeyCode::Caret,
+        "NumLock" => VirtualKeyCode::Numlock,
+        "Numpad0" => VirtualKeyCode::Numpad0,
+        "Numpad1" => VirtualKeyCode::Numpad1,
+        "Numpad2" => VirtualKeyCode::Numpad2,
+        "Numpad3" => VirtualKeyCode::Numpad3,
+        "Numpad4" => VirtualKeyCode::Numpad4,
+        "Numpad5" => VirtualKeyCode::Numpad5,
+        "Numpad6" => VirtualKeyCode::Numpad6,
+        "Numpad7" => VirtualKeyCode::Numpad7,
+        "Numpad8" => VirtualKeyCode::Numpad8,
+        "Numpad9" => VirtualKeyCode::Numpad9,
+        "AbntC1" => VirtualKeyCode::AbntC1,
+        "AbntC2" => VirtualKeyCode::AbntC2,
+        "NumpadAdd" => VirtualKeyCode::Add,
+        "Quote" => VirtualKeyCode::Apostrophe,
+        "Apps" => VirtualKeyCode::Apps,
+        "At" => VirtualKeyCode::At,
+        "Ax" => VirtualKeyCode::Ax,
+        "Backslash" => VirtualKeyCode::Backslash,
+        "Calculator" => VirtualKeyCode::Calculator,
+        "Capital" => VirtualKeyCode::Capital,
+        "Semicolon" => VirtualKeyCode::Semicolon,
+        "Comma" => VirtualKeyCode::Comma,
+        "Convert" => VirtualKeyCode::Convert,
+        "NumpadDecimal" => VirtualKeyCode::Decimal,
+        "NumpadDivide" => VirtualKeyCode::Divide,
+        "Equal" => VirtualKeyCode::Equals,
+        "Backquote" => VirtualKeyCode::Grave,
+        "Kana" => VirtualKeyCode::Kana,
+        "Kanji" => VirtualKeyCode::Kanji,
+        "AltLeft" => VirtualKeyCode::LAlt,
+        "BracketLeft" => VirtualKeyCode::LBracket,
+        "ControlLeft" => VirtualKeyCode::LControl,
+        "ShiftLeft" => VirtualKeyCode::LShift,
+        "MetaLeft" => VirtualKeyCode::LWin,
+        "Mail" => VirtualKeyCode::Mail,
+        "MediaSelect" => VirtualKeyCode::MediaSelect,
+        "MediaStop" => VirtualKeyCode::MediaStop,
+        "Minus" => VirtualKeyCode::Minus,
+        "NumpadMultiply" => VirtualKeyCode::Multiply,
+        "Mute" => VirtualKeyCode::Mute,
+        "LaunchMyComputer" => VirtualKeyCode::MyComputer,
+        "NavigateForward" => VirtualKeyCode::NavigateForward,
+        "NavigateBackward" => VirtualKeyCode::NavigateBackward,
+        "NextTrack" => VirtualKeyCode::NextTrack,
+        "NoConvert" => VirtualKeyCode::NoConvert,
+        "NumpadComma" => VirtualKeyCode::NumpadComma,
+        "NumpadEnter" => VirtualKeyCode::NumpadEnter,
+        "NumpadEquals" => VirtualKeyCode::NumpadEquals,
+        "OEM102" => VirtualKeyCode::OEM102,
+        "Period" => VirtualKeyCode::Period,
+        "PlayPause" => VirtualKeyCode::PlayPause,
+        "Power" => VirtualKeyCode::Power,
+        "PrevTrack" => VirtualKeyCode::PrevTrack,
+        "AltRight" => VirtualKeyCode::RAlt,
+        "BracketRight" => VirtualKeyCode::RBracket,
+        "ControlRight" => VirtualKeyCode::RControl,
+        "ShiftRight" => VirtualKeyCode::RShift,
+        "MetaRight" => VirtualKeyCode::RWin,
+        "Slash" => VirtualKeyCode::Slash,
+        "Sleep" => VirtualKeyCode::Sleep,
+        "Stop" => VirtualKeyCode::Stop,
+        "NumpadSubtract" => VirtualKeyCode::Subtract,
+        "Sysrq" => VirtualKeyCode::Sysrq,
+        "Tab" => VirtualKeyCode::Tab,
+        "Underline" => VirtualKeyCode::Underline,
+        "Unlabeled" => VirtualKeyCode::Unlabeled,
+        "AudioVolumeDown" => VirtualKeyCode::VolumeDown,
+        "AudioVolumeUp" => VirtualKeyCode::VolumeUp,
+        "Wake" => VirtualKeyCode::Wake,
+        "WebBack" => VirtualKeyCode::WebBack,
+        "WebFavorites" => VirtualKeyCode::WebFavorites,
+        "WebForward" => VirtualKeyCode::WebForward,
+        "WebHome" => VirtualKeyCode::WebHome,
+        "WebRefresh" => VirtualKeyCode::WebRefresh,
+        "WebSearch" => VirtualKeyCode::WebSearch,
+        "WebStop" => VirtualKeyCode::WebStop,
+        "Yen" => VirtualKeyCode::Yen,
+        "CapsLock" => VirtualKeyCode::Capital,
+        "ContextMenu" => VirtualKeyCode::Apps,
+        "IntlBackslash" => VirtualKeyCode::OEM102,
+        "IntlYen" => VirtualKeyCode::Yen,
+        "KanaMode" => VirtualKeyCode::Kana,
+        "NumpadEqual" => VirtualKeyCode::NumpadEquals,
+        "OSLeft" => VirtualKeyCode::LWin,
+        "OSRight" => VirtualKeyCode::RWin,
+        "AudioVolumeMute" | "VolumeMute" => VirtualKeyCode::Mute,
+        "VolumeDown" => VirtualKeyCode::VolumeDown,
+        "VolumeUp" => VirtualKeyCode::VolumeUp,
+        "MediaTrackNext" => VirtualKeyCode::NextTrack,
+        "MediaTrackPrevious" => VirtualKeyCode::PrevTrack,
+        "MediaPlayPause" => VirtualKeyCode::PlayPause,
+        "BrowserBack" => VirtualKeyCode::WebBack,
+        "BrowserFavorites" => VirtualKeyCode::WebFavorites,
+        "BrowserForward" => VirtualKeyCode::WebForward,
+        "BrowserHome" => VirtualKeyCode::WebHome,
+        "BrowserRefresh" => VirtualKeyCode::WebRefresh,
+        "BrowserSearch" => VirtualKeyCode::WebSearch,
+        "BrowserStop" => VirtualKeyCode::WebStop,
+        "LaunchMail" => VirtualKeyCode::Mail,
+        "LaunchApp1" => VirtualKeyCode::MyComputer,
+        "LaunchApp2" => VirtualKeyCode::Calculator,
+        "WakeUp" => VirtualKeyCode::Wake,
+        "Copy" => VirtualKeyCode::Copy,
+        "Paste" => VirtualKeyCode::Paste,
+        "Cut" => VirtualKeyCode::Cut,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every code with a scancode, and the virtual key code it has when the key doesn't tell one
+    const CODES: &[(&str, ScanCode, Option<VirtualKeyCode>)] = &[
+        ("Escape", 1, Some(VirtualKeyCode::Escape)),
+        ("Digit1", 2, Some(VirtualKeyCode::Key1)),
+        ("Digit2", 3, Some(VirtualKeyCode::Key2)),
+        ("Digit3", 4, Some(VirtualKeyCode::Key3)),
+        ("Digit4", 5, Some(VirtualKeyCode::Key4)),
+        ("Digit5", 6, Some(VirtualKeyCode::Key5)),
+        ("Digit6", 7, Some(VirtualKeyCode::Key6)),
+        ("Digit7", 8, Some(VirtualKeyCode::Key7)),
+        ("Digit8", 9, Some(VirtualKeyCode::Key8)),
+        ("Digit9", 10, Some(VirtualKeyCode::Key9)),
+        ("Digit0", 11, Some(VirtualKeyCode::Key0)),
+        ("Minus", 12, Some(VirtualKeyCode::Minus)),
+        ("Equal", 13, Some(VirtualKeyCode::Equals)),
+        ("Backspace", 14, Some(VirtualKeyCode::Back)),
+        ("Tab", 15, Some(VirtualKeyCode::Tab)),
+        ("KeyQ", 16, Some(VirtualKeyCode::Q)),
+        ("KeyW", 17, Some(VirtualKeyCode::W)),
+        ("KeyE", 18, Some(VirtualKeyCode::E)),
+        ("KeyR", 19, Some(VirtualKeyCode::R)),
+        ("KeyT", 20, Some(VirtualKeyCode::T)),
+        ("KeyY", 21, Some(VirtualKeyCode::Y)),
+        ("KeyU", 22, Some(VirtualKeyCode::U)),
+        ("KeyI", 23, Some(VirtualKeyCode::I)),
+        ("KeyO", 24, Some(VirtualKeyCode::O)),
+        ("KeyP", 25, Some(VirtualKeyCode::P)),
+        ("BracketLeft", 26, Some(VirtualKeyCode::LBracket)),
+        ("BracketRight", 27, Some(VirtualKeyCode::RBracket)),
+        ("Enter", 28, Some(VirtualKeyCode::Return)),
+        ("ControlLeft", 29, Some(VirtualKeyCode::LControl)),
+        ("KeyA", 30, Some(VirtualKeyCode::A)),
+        ("KeyS", 31, Some(VirtualKeyCode::S)),
+        ("KeyD", 32, Some(VirtualKeyCode::D)),
+        ("KeyF", 33, Some(VirtualKeyCode::F)),
+        ("KeyG", 34, Some(VirtualKeyCode::G)),
+        ("KeyH", 35, Some(VirtualKeyCode::H)),
+        ("KeyJ", 36, Some(VirtualKeyCode::J)),
+        ("KeyK", 37, Some(VirtualKeyCode::K)),
+        ("KeyL", 38, Some(VirtualKeyCode::L)),
+        ("Semicolon", 39, Some(VirtualKeyCode::Semicolon)),
+        ("Quote", 40, Some(VirtualKeyCode::Apostrophe)),
+        ("Backquote", 41, Some(VirtualKeyCode::Grave)),
+        ("ShiftLeft", 42, Some(VirtualKeyCode::LShift)),
+        ("Backslash", 43, Some(VirtualKeyCode::Backslash)),
+        ("KeyZ", 44, Some(VirtualKeyCode::Z)),
+        ("KeyX", 45, Some(VirtualKeyCode::X)),
+        ("KeyC", 46, Some(VirtualKeyCode::C)),
+        ("KeyV", 47, Some(VirtualKeyCode::V)),
+        ("KeyB", 48, Some(VirtualKeyCode::B)),
+        ("KeyN", 49, Some(VirtualKeyCode::N)),
+        ("KeyM", 50, Some(VirtualKeyCode::M)),
+        ("Comma", 51, Some(VirtualKeyCode::Comma)),
+        ("Period", 52, Some(VirtualKeyCode::Period)),
+        ("Slash", 53, Some(VirtualKeyCode::Slash)),
+        ("ShiftRight", 54, Some(VirtualKeyCode::RShift)),
+        ("NumpadMultiply", 55, Some(VirtualKeyCode::Multiply)),
+        ("AltLeft", 56, Some(VirtualKeyCode::LAlt)),
+        ("Space", 57, Some(VirtualKeyCode::Space)),
+        ("CapsLock", 58, Some(VirtualKeyCode::Capital)),
+        ("F1", 59, Some(VirtualKeyCode::F1)),
+        ("F2", 60, Some(VirtualKeyCode::F2)),
+        ("F3", 61, Some(VirtualKeyCode::F3)),
+        ("F4", 62, Some(VirtualKeyCode::F4)),
+        ("F5", 63, Some(VirtualKeyCode::F5)),
+        ("F6", 64, Some(VirtualKeyCode::F6)),
+        ("F7", 65, Some(VirtualKeyCode::F7)),
+        ("F8", 66, Some(VirtualKeyCode::F8)),
+        ("F9", 67, Some(VirtualKeyCode::F9)),
+        ("F10", 68, Some(VirtualKeyCode::F10)),
+        ("NumLock", 69, Some(VirtualKeyCode::Numlock)),
+        ("ScrollLock", 70, Some(VirtualKeyCode::Scroll)),
+        ("Numpad7", 71, Some(VirtualKeyCode::Numpad7)),
+        ("Numpad8", 72, Some(VirtualKeyCode::Numpad8)),
+        ("Numpad9", 73, Some(VirtualKeyCode::Numpad9)),
+        ("NumpadSubtract", 74, Some(VirtualKeyCode::Subtract)),
+        ("Numpad4", 75, Some(VirtualKeyCode::Numpad4)),
+        ("Numpad5", 76, Some(VirtualKeyCode::Numpad5)),
+        ("Numpad6", 77, Some(VirtualKeyCode::Numpad6)),
+        ("NumpadAdd", 78, Some(VirtualKeyCode::Add)),
+        ("Numpad1", 79, Some(VirtualKeyCode::Numpad1)),
+        ("Numpad2", 80, Some(VirtualKeyCode::Numpad2)),
+        ("Numpad3", 81, Some(VirtualKeyCode::Numpad3)),
+        ("Numpad0", 82, Some(VirtualKeyCode::Numpad0)),
+        ("NumpadDecimal", 83, Some(VirtualKeyCode::Decimal)),
+        ("IntlBackslash", 86, Some(VirtualKeyCode::OEM102)),
+        ("F11", 87, Some(VirtualKeyCode::F11)),
+        ("F12", 88, Some(VirtualKeyCode::F12)),
+        ("IntlRo", 89, None),
+        ("Lang3", 90, None),
+        ("Lang4", 91, None),
+        ("Convert", 92, Some(VirtualKeyCode::Convert)),
+        ("KanaMode", 93, Some(VirtualKeyCode::Kana)),
+        ("NonConvert", 94, None),
+        ("NumpadEnter", 96, Some(VirtualKeyCode::NumpadEnter)),
+        ("ControlRight", 97, Some(VirtualKeyCode::RControl)),
+        ("NumpadDivide", 98, Some(VirtualKeyCode::Divide)),
+        ("PrintScreen", 99, Some(VirtualKeyCode::Snapshot)),
+        ("AltRight", 100, Some(VirtualKeyCode::RAlt)),
+        ("Home", 102, Some(VirtualKeyCode::Home)),
+        ("ArrowUp", 103, Some(VirtualKeyCode::Up)),
+        ("PageUp", 104, Some(VirtualKeyCode::PageUp)),
+        ("ArrowLeft", 105, Some(VirtualKeyCode::Left)),
+        ("ArrowRight", 106, Some(VirtualKeyCode::Right)),
+        ("End", 107, Some(VirtualKeyCode::End)),
+        ("ArrowDown", 108, Some(VirtualKeyCode::Down)),
+        ("PageDown", 109, Some(VirtualKeyCode::PageDown)),
+        ("Insert", 110, Some(VirtualKeyCode::Insert)),
+        ("Delete", 111, Some(VirtualKeyCode::Delete)),
+        ("AudioVolumeMute", 113, Some(VirtualKeyCode::Mute)),
+        ("VolumeMute", 113, Some(VirtualKeyCode::Mute)),
+        ("AudioVolumeDown", 114, Some(VirtualKeyCode::VolumeDown)),
+        ("VolumeDown", 114, Some(VirtualKeyCode::VolumeDown)),
+        ("AudioVolumeUp", 115, Some(VirtualKeyCode::VolumeUp)),
+        ("VolumeUp", 115, Some(VirtualKeyCode::VolumeUp)),
+        ("Power", 116, Some(VirtualKeyCode::Power)),
+        ("NumpadEqual", 117, Some(VirtualKeyCode::NumpadEquals)),
+        ("Pause", 119, Some(VirtualKeyCode::Pause)),
+        ("NumpadComma", 121, Some(VirtualKeyCode::NumpadComma)),
+        ("Lang1", 122, None),
+        ("Lang2", 123, None),
+        ("IntlYen", 124, Some(VirtualKeyCode::Yen)),
+        ("MetaLeft", 125, Some(VirtualKeyCode::LWin)),
+        ("OSLeft", 125, Some(VirtualKeyCode::LWin)),
+        ("MetaRight", 126, Some(VirtualKeyCode::RWin)),
+        ("OSRight", 126, Some(VirtualKeyCode::RWin)),
+        ("ContextMenu", 127, Some(VirtualKeyCode::Apps)),
+        ("BrowserStop", 128, Some(VirtualKeyCode::WebStop)),
+        ("Again", 129, None),
+        ("Props", 130, None),
+        ("Undo", 131, None),
+        ("Copy", 133, Some(VirtualKeyCode::Copy)),
+        ("Open", 134, None),
+        ("Paste", 135, Some(VirtualKeyCode::Paste)),
+        ("Find", 136, None),
+        ("Cut", 137, Some(VirtualKeyCode::Cut)),
+        ("Help", 138, None),
+        ("LaunchApp2", 140, Some(VirtualKeyCode::Calculator)),
+        ("Sleep", 142, Some(VirtualKeyCode::Sleep)),
+        ("WakeUp", 143, Some(VirtualKeyCode::Wake)),
+        ("LaunchApp1", 144, Some(VirtualKeyCode::MyComputer)),
+        ("LaunchMail", 155, Some(VirtualKeyCode::Mail)),
+        ("BrowserFavorites", 156, Some(VirtualKeyCode::WebFavorites)),
+        ("BrowserBack", 158, Some(VirtualKeyCode::WebBack)),
+        ("BrowserForward", 159, Some(VirtualKeyCode::WebForward)),
+        ("Eject", 161, None),
+        ("MediaTrackNext", 163, Some(VirtualKeyCode::NextTrack)),
+        ("MediaPlayPause", 164, Some(VirtualKeyCode::PlayPause)),
+        ("MediaTrackPrevious", 165, Some(VirtualKeyCode::PrevTrack)),
+        ("MediaStop", 166, Some(VirtualKeyCode::MediaStop)),
+        ("MediaSelect", 171, Some(VirtualKeyCode::MediaSelect)),
+        ("BrowserHome", 172, Some(VirtualKeyCode::WebHome)),
+        ("BrowserRefresh", 173, Some(VirtualKeyCode::WebRefresh)),
+        ("NumpadParenLeft", 179, None),
+        ("NumpadParenRight", 180, None),
+        ("F13", 183, Some(VirtualKeyCode::F13)),
+        ("F14", 184, Some(VirtualKeyCode::F14)),
+        ("F15", 185, Some(VirtualKeyCode::F15)),
+        ("F16", 186, Some(VirtualKeyCode::F16)),
+        ("F17", 187, Some(VirtualKeyCode::F17)),
+        ("F18", 188, Some(VirtualKeyCode::F18)),
+        ("F19", 189, Some(VirtualKeyCode::F19)),
+        ("F20", 190, Some(VirtualKeyCode::F20)),
+        ("F21", 191, Some(VirtualKeyCode::F21)),
+        ("F22", 192, Some(VirtualKeyCode::F22)),
+        ("F23", 193, Some(VirtualKeyCode::F23)),
+        ("F24", 194, Some(VirtualKeyCode::F24)),
+        ("BrowserSearch", 217, Some(VirtualKeyCode::WebSearch)),
+        ("Fn", 464, None),
+    ];
+
+    // Every key with a virtual key code, regardless of the code of the key it is typed with
+    const KEYS: &[(&str, VirtualKeyCode)] = &[
+        ("Escape", VirtualKeyCode::Escape),
+        ("Esc", VirtualKeyCode::Escape),
+        ("F1", VirtualKeyCode::F1),
+        ("F2", VirtualKeyCode::F2),
+        ("F3", VirtualKeyCode::F3),
+        ("F4", VirtualKeyCode::F4),
+        ("F5", VirtualKeyCode::F5),
+        ("F6", VirtualKeyCode::F6),
+        ("F7", VirtualKeyCode::F7),
+        ("F8", VirtualKeyCode::F8),
+        ("F9", VirtualKeyCode::F9),
+        ("F10", VirtualKeyCode::F10),
+        ("F11", VirtualKeyCode::F11),
+        ("F12", VirtualKeyCode::F12),
+        ("F13", VirtualKeyCode::F13),
+        ("F14", VirtualKeyCode::F14),
+        ("F15", VirtualKeyCode::F15),
+        ("F16", VirtualKeyCode::F16),
+        ("F17", VirtualKeyCode::F17),
+        ("F18", VirtualKeyCode::F18),
+        ("F19", VirtualKeyCode::F19),
+        ("F20", VirtualKeyCode::F20),
+        ("F21", VirtualKeyCode::F21),
+        ("F22", VirtualKeyCode::F22),
+        ("F23", VirtualKeyCode::F23),
+        ("F24", VirtualKeyCode::F24),
+        ("PrintScreen", VirtualKeyCode::Snapshot),
+        ("ScrollLock", VirtualKeyCode::Scroll),
+        ("Scroll", VirtualKeyCode::Scroll),
+        ("Pause", VirtualKeyCode::Pause),
+        ("Insert", VirtualKeyCode::Insert),
+        ("Home", VirtualKeyCode::Home),
+        ("Delete", VirtualKeyCode::Delete),
+        ("Del", VirtualKeyCode::Delete),
+        ("End", VirtualKeyCode::End),
+        ("PageDown", VirtualKeyCode::PageDown),
+        ("PageUp", VirtualKeyCode::PageUp),
+        ("ArrowLeft", VirtualKeyCode::Left),
+        ("Left", VirtualKeyCode::Left),
+        ("ArrowUp", VirtualKeyCode::Up),
+        ("Up", VirtualKeyCode::Up),
+        ("ArrowRight", VirtualKeyCode::Right),
+        ("Right", VirtualKeyCode::Right),
+        ("ArrowDown", VirtualKeyCode::Down),
+        ("Down", VirtualKeyCode::Down),
+        ("Backspace", VirtualKeyCode::Back),
+        ("Enter", VirtualKeyCode::Return),
+        ("Tab", VirtualKeyCode::Tab),
+        ("Compose", VirtualKeyCode::Compose),
+        ("CapsLock", VirtualKeyCode::Capital),
+        ("ContextMenu", VirtualKeyCode::Apps),
+        ("Apps", VirtualKeyCode::Apps),
+        ("Convert", VirtualKeyCode::Convert),
+        ("NonConvert", VirtualKeyCode::NoConvert),
+        ("KanaMode", VirtualKeyCode::Kana),
+        ("KanjiMode", VirtualKeyCode::Kanji),
+        ("AudioVolumeMute", VirtualKeyCode::Mute),
+        ("VolumeMute", VirtualKeyCode::Mute),
+        ("AudioVolumeDown", VirtualKeyCode::VolumeDown),
+        ("VolumeDown", VirtualKeyCode::VolumeDown),
+        ("AudioVolumeUp", VirtualKeyCode::VolumeUp),
+        ("VolumeUp", VirtualKeyCode::VolumeUp),
+        ("MediaTrackNext", VirtualKeyCode::NextTrack),
+        ("MediaNextTrack", VirtualKeyCode::NextTrack),
+        ("MediaTrackPrevious", VirtualKeyCode::PrevTrack),
+        ("MediaPreviousTrack", VirtualKeyCode::PrevTrack),
+        ("MediaPlayPause", VirtualKeyCode::PlayPause),
+        ("MediaStop", VirtualKeyCode::MediaStop),
+        ("BrowserBack", VirtualKeyCode::WebBack),
+        ("BrowserFavorites", VirtualKeyCode::WebFavorites),
+        ("BrowserForward", VirtualKeyCode::WebForward),
+        ("BrowserHome", VirtualKeyCode::WebHome),
+        ("BrowserRefresh", VirtualKeyCode::WebRefresh),
+        ("BrowserSearch", VirtualKeyCode::WebSearch),
+        ("BrowserStop", VirtualKeyCode::WebStop),
+        ("LaunchMail", VirtualKeyCode::Mail),
+        ("LaunchApplication1", VirtualKeyCode::MyComputer),
+        ("LaunchMyComputer", VirtualKeyCode::MyComputer),
+        ("LaunchApplication2", VirtualKeyCode::Calculator),
+        ("LaunchCalculator", VirtualKeyCode::Calculator),
+        ("Power", VirtualKeyCode::Power),
+        ("Standby", VirtualKeyCode::Sleep),
+        ("WakeUp", VirtualKeyCode::Wake),
+        ("Copy", VirtualKeyCode::Copy),
+        ("Paste", VirtualKeyCode::Paste),
+        ("Cut", VirtualKeyCode::Cut),
+        ("1", VirtualKeyCode::Key1),
+        ("2", VirtualKeyCode::Key2),
+        ("3", VirtualKeyCode::Key3),
+        ("4", VirtualKeyCode::Key4),
+        ("5", VirtualKeyCode::Key5),
+        ("6", VirtualKeyCode::Key6),
+        ("7", VirtualKeyCode::Key7),
+        ("8", VirtualKeyCode::Key8),
+        ("9", VirtualKeyCode::Key9),
+        ("0", VirtualKeyCode::Key0),
+        ("a", VirtualKeyCode::A),
+        ("b", VirtualKeyCode::B),
+        ("c", VirtualKeyCode::C),
+        ("d", VirtualKeyCode::D),
+        ("e", VirtualKeyCode::E),
+        ("f", VirtualKeyCode::F),
+        ("g", VirtualKeyCode::G),
+        ("h", VirtualKeyCode::H),
+        ("i", VirtualKeyCode::I),
+        ("j", VirtualKeyCode::J),
+        ("k", VirtualKeyCode::K),
+        ("l", VirtualKeyCode::L),
+        ("m", VirtualKeyCode::M),
+        ("n", VirtualKeyCode::N),
+        ("o", VirtualKeyCode::O),
+        ("p", VirtualKeyCode::P),
+        ("q", VirtualKeyCode::Q),
+        ("r", VirtualKeyCode::R),
+        ("s", VirtualKeyCode::S),
+        ("t", VirtualKeyCode::T),
+        ("u", VirtualKeyCode::U),
+        ("v", VirtualKeyCode::V),
+        ("w", VirtualKeyCode::W),
+        ("x", VirtualKeyCode::X),
+        ("y", VirtualKeyCode::Y),
+        ("z", VirtualKeyCode::Z),
+        (" ", VirtualKeyCode::Space),
+        ("'", VirtualKeyCode::Apostrophe),
+        ("@", VirtualKeyCode::At),
+        ("\\", VirtualKeyCode::Backslash),
+        ("^", VirtualKeyCode::Caret),
+        (":", VirtualKeyCode::Colon),
+        (",", VirtualKeyCode::Comma),
+        ("=", VirtualKeyCode::Equals),
+        ("`", VirtualKeyCode::Grave),
+        ("[", VirtualKeyCode::LBracket),
+        ("-", VirtualKeyCode::Minus),
+        (".", VirtualKeyCode::Period),
+        ("]", VirtualKeyCode::RBracket),
+        (";", VirtualKeyCode::Semicolon),
+        ("/", VirtualKeyCode::Slash),
+        ("_", VirtualKeyCode::Underline),
+        ("¥", VirtualKeyCode::Yen),
+    ];
+
+    #[test]
+    fn code_mapping() {
+        for &(code, scancode, virtual_keycode) in CODES {
+            let location = KeyLocation::Standard;
+            assert_eq!(
+                scan_code(code, "Unidentified", location),
+                scancode,
+                "{}",
+                code
+            );
+            assert_eq!(
+                virtual_key_code(code, "Unidentified", location),
+                virtual_keycode,
+                "{}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn key_mapping() {
+        for &(key, virtual_keycode) in KEYS {
+            let location = KeyLocation::Standard;
+            assert_eq!(
+                virtual_key_code("", key, location),
+                Some(virtual_keycode),
+                "{}",
+                key
+            );
+            assert_eq!(scan_code("", key, location), 0, "{}", key);
+        }
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let location = KeyLocation::Standard;
+        assert_eq!(scan_code("", "Unidentified", location), 0);
+        assert_eq!(scan_code("NotACode", "Unidentified", location), 0);
+        assert_eq!(virtual_key_code("", "Unidentified", location), None);
+        assert_eq!(virtual_key_code("", "Dead", location), None);
+    }
+
+    // On an AZERTY keyboard, the key in the place of Q on a US keyboard types A
+    #[test]
+    fn layout_independent_scancodes() {
+        let location = KeyLocation::Standard;
+        for &(code, key, scancode, virtual_keycode) in &[
+            ("KeyQ", "a", 16, VirtualKeyCode::A),
+            ("KeyW", "z", 17, VirtualKeyCode::Z),
+            ("KeyA", "q", 30, VirtualKeyCode::Q),
+            ("KeyZ", "w", 44, VirtualKeyCode::W),
+            ("KeyY", "z", 21, VirtualKeyCode::Z),
+            ("KeyZ", "y", 44, VirtualKeyCode::Y),
+            ("Semicolon", "m", 39, VirtualKeyCode::M),
+            ("KeyA", "A", 30, VirtualKeyCode::A),
+        ] {
+            assert_eq!(scan_code(code, key, location), scancode, "{}", code);
+            assert_eq!(
+                virtual_key_code(code, key, location),
+                Some(virtual_keycode),
+                "{}",
+                code
+            );
+        }
+    }
+
+    // Keys producing characters without a virtual key code fall back to their position, e.g. the
+    // digits of an AZERTY keyboard that need Shift
+    #[test]
+    fn characters_fall_back_to_the_code() {
+        let location = KeyLocation::Standard;
+        assert_eq!(
+            virtual_key_code("Digit1", "&", location),
+            Some(VirtualKeyCode::Key1)
+        );
+        assert_eq!(
+            virtual_key_code("Digit2", "é", location),
+            Some(VirtualKeyCode::Key2)
+        );
+        assert_eq!(
+            virtual_key_code("BracketLeft", "Dead", location),
+            Some(VirtualKeyCode::LBracket)
+        );
+    }
+
+    #[test]
+    fn located_keys() {
+        for &(key, location, scancode, virtual_keycode) in &[
+            ("Shift", KeyLocation::Left, 42, VirtualKeyCode::LShift),
+            ("Shift", KeyLocation::Right, 54, VirtualKeyCode::RShift),
+            ("Control", KeyLocation::Left, 29, VirtualKeyCode::LControl),
+            ("Control", KeyLocation::Right, 97, VirtualKeyCode::RControl),
+            ("Alt", KeyLocation::Left, 56, VirtualKeyCode::LAlt),
+            ("AltGraph", KeyLocation::Right, 100, VirtualKeyCode::RAlt),
+            ("Meta", KeyLocation::Left, 125, VirtualKeyCode::LWin),
+            ("OS", KeyLocation::Right, 126, VirtualKeyCode::RWin),
+            ("7", KeyLocation::Numpad, 71, VirtualKeyCode::Numpad7),
+            ("Home", KeyLocation::Numpad, 71, VirtualKeyCode::Numpad7),
+            ("Delete", KeyLocation::Numpad, 83, VirtualKeyCode::Decimal),
+            (
+                "Enter",
+                KeyLocation::Numpad,
+                96,
+                VirtualKeyCode::NumpadEnter,
+            ),
+            ("/", KeyLocation::Numpad, 98, VirtualKeyCode::Divide),
+        ] {
+            assert_eq!(scan_code("", key, location), scancode, "{}", key);
+            assert_eq!(
+                virtual_key_code("", key, location),
+                Some(virtual_keycode),
+                "{}",
+                key
+            );
+        }
+
+        // The code takes precedence over the key, which may be changed by Num Lock
+        let location = KeyLocation::Numpad;
+        assert_eq!(
+            virtual_key_code("Numpad8", "ArrowUp", location),
+            Some(VirtualKeyCode::Numpad8)
+        );
+        assert_eq!(scan_code("Numpad8", "ArrowUp", location), 72);
+    }
+}
@@ -1,11 +1,10 @@
 // Brief introduction to the internals of the web backend:
-// Currently, the web backend supports both wasm-bindgen and stdweb as methods of binding to the
-// environment. Because they are both supporting the same underlying APIs, the actual web bindings
-// are cordoned off into backend abstractions, which present the thinnest unifying layer possible.
+// The web backend binds to the environment through wasm-bindgen and web-sys. The actual web
+// bindings are cordoned off into a backend abstraction, which presents the thinnest layer possible.
 //
 // When adding support for new events or interactions with the browser, first consult trusted
 // documentation (such as MDN) to ensure it is well-standardised and supported across many browsers.
-// Once you have decided on the relevant web APIs, add support to both backends.
+// Once you have decided on the relevant web APIs, add support to the backend.
 //
 // The backend is used by the rest of the module to implement Winit's business logic, which forms
 // the rest of the code. 'device', 'error', 'monitor', and 'window' define web-specific structures
@@ -17,9 +16,11 @@
 // incoming events (from the registered handlers) and ensuring they are passed to the user in a
 // compliant way.
 
+mod clipboard;
 mod device;
 mod error;
 mod event_loop;
+mod keyboard;
 mod monitor;
 mod window;
 
@@ -27,13 +28,10 @@ mod window;
 #[path = "web_sys/mod.rs"]
 mod backend;
 
-#[cfg(feature = "stdweb")]
-#[path = "stdweb/mod.rs"]
-mod backend;
-
-#[cfg(not(any(feature = "web-sys", feature = "stdweb")))]
-compile_error!("Please select a feature to build for web: `web-sys`, `stdweb`");
+#[cfg(not(feature = "web-sys"))]
+compile_error!("Please select the `web-sys` feature to build for web");
 
+pub use self::clipboard::Clipboard;
 pub use self::device::Id as DeviceId;
 pub use self::error::OsError;
 pub use self::event_loop::{
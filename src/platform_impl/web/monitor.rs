@@ -1,12 +1,15 @@
+use super::backend;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::monitor::{MonitorHandle, VideoMode};
 
+// The page can only see the screen it is currently displayed on, whose properties are queried
+// every time they are requested, as the page may be moved to another screen
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Handle;
 
 impl Handle {
     pub fn scale_factor(&self) -> f64 {
-        1.0
+        backend::scale_factor()
     }
 
     pub fn position(&self) -> PhysicalPosition<i32> {
@@ -18,14 +21,12 @@ impl Handle {
     }
 
     pub fn size(&self) -> PhysicalSize<u32> {
-        PhysicalSize {
-            width: 0,
-            height: 0,
-        }
+        backend::screen_size().to_physical(self.scale_factor())
     }
 
+    // The video mode can't be changed, so the current one is the only one
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
-        std::iter::empty()
+        std::iter::once(VideoMode { video_mode: Mode })
     }
 }
 
@@ -34,15 +35,16 @@ pub struct Mode;
 
 impl Mode {
     pub fn size(&self) -> PhysicalSize<u32> {
-        unimplemented!();
+        self.monitor().size()
     }
 
     pub fn bit_depth(&self) -> u16 {
-        unimplemented!();
+        backend::color_depth()
     }
 
+    // Browsers don't expose the refresh rate, so the most common one is assumed
     pub fn refresh_rate(&self) -> u16 {
-        32
+        60
     }
 
     pub fn monitor(&self) -> MonitorHandle {
@@ -1,43 +1,155 @@
+use super::drag::Drag;
 use super::event;
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use super::event_handle::EventListenerHandle;
+use super::frame::Frame;
+use super::ime::Ime;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use crate::error::OsError as RootOE;
-use crate::event::{ModifiersState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode};
+use crate::event::{
+    Force, ModifiersState, MouseButton, MouseScrollDelta, ScanCode, TouchPhase, VirtualKeyCode,
+};
+use crate::platform::web::{CursorMoveCoalescing, PointerType};
 use crate::platform_impl::{OsError, PlatformSpecificWindowBuilderAttributes};
+use crate::window::Orientation;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use js_sys::Array;
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::{Event, FocusEvent, HtmlCanvasElement, KeyboardEvent, PointerEvent, WheelEvent, UiEvent, BeforeUnloadEvent, AddEventListenerOptions};
+use web_sys::{
+    AddEventListenerOptions, BeforeUnloadEvent, CompositionEvent, DragEvent, Event, EventTarget,
+    File, FocusEvent, HtmlCanvasElement, HtmlElement, InputEvent, KeyboardEvent,
+    MediaQueryListEvent, PageTransitionEvent, PointerEvent, ResizeObserver,
+    ResizeObserverBoxOptions, ResizeObserverOptions, TouchEvent, UiEvent, WheelEvent,
+};
+
+type ScaleFactorHandler = Box<dyn FnMut(f64)>;
+type ResolutionListener = Rc<RefCell<Option<EventListenerHandle<dyn FnMut(MediaQueryListEvent)>>>>;
 
 pub struct Canvas {
     /// Note: resizing the HTMLCanvasElement should go through `backend::set_canvas_size` to ensure the DPI factor is maintained.
     raw: HtmlCanvasElement,
-    on_focus: Option<Closure<dyn FnMut(FocusEvent)>>,
-    on_blur: Option<Closure<dyn FnMut(FocusEvent)>>,
-    on_keyboard_release: Option<Closure<dyn FnMut(KeyboardEvent)>>,
-    on_keyboard_press: Option<Closure<dyn FnMut(KeyboardEvent)>>,
-    on_received_character: Option<Closure<dyn FnMut(KeyboardEvent)>>,
-    on_cursor_leave: Option<Closure<dyn FnMut(PointerEvent)>>,
-    on_cursor_enter: Option<Closure<dyn FnMut(PointerEvent)>>,
-    on_cursor_move: Option<Closure<dyn FnMut(PointerEvent)>>,
-    on_mouse_press: Option<Closure<dyn FnMut(PointerEvent)>>,
-    on_mouse_release: Option<Closure<dyn FnMut(PointerEvent)>>,
-    on_mouse_wheel: Option<Closure<dyn FnMut(WheelEvent)>>,
-    on_fullscreen_change: Option<Closure<dyn FnMut(Event)>>,
-    on_before_unload: Option<Closure<dyn FnMut(BeforeUnloadEvent)>>,
-    on_resize: Option<Closure<dyn FnMut(UiEvent)>>,
-    wants_fullscreen: Rc<RefCell<bool>>,
+    ime: Ime,
+    frame: Option<Frame>,
+    drag: Drag,
+    pointer_down: Rc<RefCell<Option<PointerEvent>>>,
+    on_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_ime_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_ime_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_keyboard_release: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_keyboard_press: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_received_character: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_ime_keyboard_release: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_ime_keyboard_press: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_ime_received_character: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_ime_input: Option<EventListenerHandle<dyn FnMut(InputEvent)>>,
+    on_composition_start: Option<EventListenerHandle<dyn FnMut(CompositionEvent)>>,
+    on_composition_update: Option<EventListenerHandle<dyn FnMut(CompositionEvent)>>,
+    on_composition_end: Option<EventListenerHandle<dyn FnMut(CompositionEvent)>>,
+    on_cursor_leave: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_cursor_enter: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_cursor_move: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_mouse_press: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_mouse_release: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_mouse_wheel: Option<EventListenerHandle<dyn FnMut(WheelEvent)>>,
+    on_touch_start: Option<EventListenerHandle<dyn FnMut(TouchEvent)>>,
+    on_touch_move: Option<EventListenerHandle<dyn FnMut(TouchEvent)>>,
+    on_touch_end: Option<EventListenerHandle<dyn FnMut(TouchEvent)>>,
+    on_touch_cancel: Option<EventListenerHandle<dyn FnMut(TouchEvent)>>,
+    on_fullscreen_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_before_unload: Option<EventListenerHandle<dyn FnMut(BeforeUnloadEvent)>>,
+    on_page_hide: Option<EventListenerHandle<dyn FnMut(PageTransitionEvent)>>,
+    on_page_show: Option<EventListenerHandle<dyn FnMut(PageTransitionEvent)>>,
+    on_freeze: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_resume: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_resize: Option<EventListenerHandle<dyn FnMut(UiEvent)>>,
+    on_scroll: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_resolution_change: ResolutionListener,
+    on_size_change: Option<Closure<dyn FnMut(Array)>>,
+    resize_observer: Option<ResizeObserver>,
+    on_pointer_lock_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_pointer_lock_error: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_drag_enter: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_over: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_leave: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drop: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_dark_mode: Option<EventListenerHandle<dyn FnMut(MediaQueryListEvent)>>,
+    on_visibility_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_context_lost: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_context_restored: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_context_menu: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_orientation_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_gesture_start: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_gesture_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_gesture_end: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_pen_down: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_pen_move: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_pen_up: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_pen_cancel: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    dropped_files: Rc<RefCell<Vec<File>>>,
+    handling_user_event: Rc<Cell<bool>>,
+    ignore_key_repeat: bool,
+    prevented_keys: Rc<[(VirtualKeyCode, ModifiersState)]>,
+    cursor_move_coalescing: CursorMoveCoalescing,
+    passive_listeners: bool,
+    prevent_default: bool,
+    prevent_context_menu: bool,
+    pointer_capture: bool,
+    ime_allowed: Rc<Cell<bool>>,
+    // Whether a character typed into the IME element was reported since its last `input` event
+    ime_typed: Rc<Cell<bool>>,
+    ime_position: Rc<Cell<LogicalPosition<f64>>>,
+    scale_factor_override: Rc<Cell<Option<f64>>>,
+    on_scale_factor_override: RefCell<Option<ScaleFactorHandler>>,
+    size_constraints: Rc<SizeConstraints>,
+    locked_cursor_position: Rc<Cell<PhysicalPosition<f64>>>,
+    auto_size: Rc<Cell<bool>>,
+    is_visible: Rc<Cell<bool>>,
+    confirm_unload: Rc<Cell<bool>>,
+    shown_display: RefCell<String>,
+    restored_styles: RefCell<Option<Vec<(HtmlElement, &'static str, String)>>>,
+    // Whether the canvas was created by winit rather than provided by the user, who keeps it
+    created: bool,
+}
+
+/// The minimum and maximum inner size of a window.
+#[derive(Default)]
+pub struct SizeConstraints {
+    min: Cell<Option<Size>>,
+    max: Cell<Option<Size>>,
+}
+
+impl SizeConstraints {
+    pub fn clamp(&self, size: PhysicalSize<u32>, scale_factor: f64) -> PhysicalSize<u32> {
+        let mut size = size;
+        if let Some(max) = self.max.get() {
+            let max = max.to_physical::<u32>(scale_factor);
+            size.width = size.width.min(max.width);
+            size.height = size.height.min(max.height);
+        }
+        if let Some(min) = self.min.get() {
+            let min = min.to_physical::<u32>(scale_factor);
+            size.width = size.width.max(min.width);
+            size.height = size.height.max(min.height);
+        }
+        size
+    }
 }
 
 impl Drop for Canvas {
     fn drop(&mut self) {
-        self.raw.remove();
+        self.remove_listeners();
+        if self.created {
+            self.raw.remove();
+        }
     }
 }
 
 impl Canvas {
     pub fn create(attr: PlatformSpecificWindowBuilderAttributes) -> Result<Self, RootOE> {
+        let created = attr.canvas.is_none();
         let canvas = match attr.canvas {
             Some(canvas) => canvas,
             None => {
@@ -60,41 +172,484 @@ impl Canvas {
         // sequential keyboard navigation, but its order is defined by the
         // document's source order.
         // https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/tabindex
-        canvas
-            .set_attribute("tabindex", "0")
-            .map_err(|_| os_error!(OsError("Failed to set a tabindex".to_owned())))?;
+        // A tabindex already present on a canvas provided by the user is left untouched, unless
+        // one was requested explicitly.
+        match attr.tab_index {
+            Some(tab_index) => canvas.set_tab_index(tab_index),
+            None if !canvas.has_attribute("tabindex") => canvas.set_tab_index(0),
+            None => (),
+        }
+
+        // The canvas is added to the container right away, so that the frame drawing the
+        // decorations takes its place there
+        if let Some(container) = attr.container {
+            container.append_child(&canvas).map_err(|_| {
+                os_error!(OsError("Failed to append canvas to container".to_owned()))
+            })?;
+        }
 
         // contenteditable is needed to the correct keys from deadkeys ('`' + 'e' -> 'è')
         canvas
             .set_attribute("contenteditable", "true")
             .map_err(|_| os_error!(OsError("Failed to set a contenteditable".to_owned())))?;
 
+        // The canvas keeps its place in the layout while hidden, so that nothing moves once the
+        // first frame is drawn
+        if attr.hidden_until_ready {
+            canvas
+                .style()
+                .set_property("visibility", "hidden")
+                .map_err(|_| os_error!(OsError("Failed to hide the canvas".to_owned())))?;
+        }
+
+        // Browsers ignore `preventDefault` in passive listeners, and may start panning or zooming
+        // before a touch listener gets to prevent it, but don't for elements with `touch-action:
+        // none`
+        if attr.prevent_touch_action {
+            canvas
+                .style()
+                .set_property("touch-action", "none")
+                .map_err(|_| os_error!(OsError("Failed to set the touch action".to_owned())))?;
+        }
+
+        let ime = Ime::create()?;
+        let drag = Drag::new(canvas.as_ref());
+
         Ok(Canvas {
             raw: canvas,
+            ime,
+            frame: None,
+            drag,
+            pointer_down: Rc::new(RefCell::new(None)),
             on_blur: None,
             on_focus: None,
+            on_ime_blur: None,
+            on_ime_focus: None,
             on_keyboard_release: None,
             on_keyboard_press: None,
             on_received_character: None,
+            on_ime_keyboard_release: None,
+            on_ime_keyboard_press: None,
+            on_ime_received_character: None,
+            on_ime_input: None,
+            on_composition_start: None,
+            on_composition_update: None,
+            on_composition_end: None,
             on_cursor_leave: None,
             on_cursor_enter: None,
             on_cursor_move: None,
             on_mouse_release: None,
             on_mouse_press: None,
             on_mouse_wheel: None,
+            on_touch_start: None,
+            on_touch_move: None,
+            on_touch_end: None,
+            on_touch_cancel: None,
             on_fullscreen_change: None,
             on_resize: None,
+            on_scroll: None,
+            on_resolution_change: Rc::new(RefCell::new(None)),
+            on_size_change: None,
+            resize_observer: None,
             on_before_unload: None,
-            wants_fullscreen: Rc::new(RefCell::new(false)),
+            on_page_hide: None,
+            on_page_show: None,
+            on_freeze: None,
+            on_resume: None,
+            on_pointer_lock_change: None,
+            on_pointer_lock_error: None,
+            on_drag_enter: None,
+            on_drag_over: None,
+            on_drag_leave: None,
+            on_drop: None,
+            on_dark_mode: None,
+            on_visibility_change: None,
+            on_context_lost: None,
+            on_context_restored: None,
+            on_context_menu: None,
+            on_orientation_change: None,
+            on_gesture_start: None,
+            on_gesture_change: None,
+            on_gesture_end: None,
+            on_pen_down: None,
+            on_pen_move: None,
+            on_pen_up: None,
+            on_pen_cancel: None,
+            dropped_files: Rc::new(RefCell::new(Vec::new())),
+            handling_user_event: Rc::new(Cell::new(false)),
+            ignore_key_repeat: attr.ignore_key_repeat,
+            prevented_keys: attr.prevented_keys.into(),
+            cursor_move_coalescing: attr.cursor_move_coalescing,
+            passive_listeners: attr.passive_listeners.unwrap_or(false),
+            prevent_default: attr.prevent_default.unwrap_or(false),
+            prevent_context_menu: attr.prevent_context_menu,
+            pointer_capture: attr.pointer_capture,
+            ime_allowed: Rc::new(Cell::new(true)),
+            ime_typed: Rc::new(Cell::new(false)),
+            ime_position: Rc::new(Cell::new(LogicalPosition { x: 0.0, y: 0.0 })),
+            scale_factor_override: Rc::new(Cell::new(None)),
+            on_scale_factor_override: RefCell::new(None),
+            size_constraints: Rc::new(SizeConstraints::default()),
+            locked_cursor_position: Rc::new(Cell::new(PhysicalPosition { x: 0.0, y: 0.0 })),
+            auto_size: Rc::new(Cell::new(!attr.manual_size)),
+            is_visible: Rc::new(Cell::new(true)),
+            confirm_unload: Rc::new(Cell::new(false)),
+            shown_display: RefCell::new(String::new()),
+            restored_styles: RefCell::new(None),
+            created,
         })
     }
 
+    // Detaches every listener registered on the canvas, its IME element, the document and the
+    // window, after which the handlers are never called again
+    pub fn remove_listeners(&mut self) {
+        self.on_focus = None;
+        self.on_blur = None;
+        self.on_ime_focus = None;
+        self.on_ime_blur = None;
+        self.on_keyboard_release = None;
+        self.on_keyboard_press = None;
+        self.on_received_character = None;
+        self.on_ime_keyboard_release = None;
+        self.on_ime_keyboard_press = None;
+        self.on_ime_received_character = None;
+        self.on_ime_input = None;
+        self.on_composition_start = None;
+        self.on_composition_update = None;
+        self.on_composition_end = None;
+        self.on_cursor_leave = None;
+        self.on_cursor_enter = None;
+        self.on_cursor_move = None;
+        self.on_mouse_press = None;
+        self.on_mouse_release = None;
+        self.on_mouse_wheel = None;
+        self.on_touch_start = None;
+        self.on_touch_move = None;
+        self.on_touch_end = None;
+        self.on_touch_cancel = None;
+        self.on_fullscreen_change = None;
+        self.on_before_unload = None;
+        self.on_page_hide = None;
+        self.on_page_show = None;
+        self.on_freeze = None;
+        self.on_resume = None;
+        self.on_resize = None;
+        self.on_scroll = None;
+        self.on_resolution_change.borrow_mut().take();
+        self.on_pointer_lock_change = None;
+        self.on_pointer_lock_error = None;
+        self.on_drag_enter = None;
+        self.on_drag_over = None;
+        self.on_drag_leave = None;
+        self.on_drop = None;
+        self.on_dark_mode = None;
+        self.on_visibility_change = None;
+        self.on_context_lost = None;
+        self.on_context_restored = None;
+        self.on_context_menu = None;
+        self.on_orientation_change = None;
+        self.on_gesture_start = None;
+        self.on_gesture_change = None;
+        self.on_gesture_end = None;
+        self.on_pen_down = None;
+        self.on_pen_move = None;
+        self.on_pen_up = None;
+        self.on_pen_cancel = None;
+        if let Some(resize_observer) = self.resize_observer.take() {
+            resize_observer.disconnect();
+        }
+        self.on_size_change = None;
+        *self.on_scale_factor_override.borrow_mut() = None;
+    }
+
+    // The scale factor of the canvas is the device pixel ratio, unless it has been overridden
+    pub fn scale_factor(&self) -> f64 {
+        scale_factor(&self.scale_factor_override)
+    }
+
+    pub fn set_scale_factor_override(&self, scale_factor_override: Option<f64>) {
+        self.scale_factor_override.set(scale_factor_override);
+
+        let scale_factor = self.scale_factor();
+        if let Ok(mut handler) = self.on_scale_factor_override.try_borrow_mut() {
+            if let Some(handler) = handler.as_mut() {
+                handler(scale_factor);
+            }
+        }
+    }
+
+    // The handler receives the new scale factor when the override is changed
+    pub fn on_scale_factor_override<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(f64),
+    {
+        *self.on_scale_factor_override.borrow_mut() = Some(Box::new(handler));
+    }
+
     pub fn set_attribute(&self, attribute: &str, value: &str) {
         self.raw
             .set_attribute(attribute, value)
             .expect(&format!("Set attribute: {}", attribute));
     }
 
+    // The frame takes the place of the canvas in the document, and gives it back when removed. A
+    // frame the canvas was moved out of is replaced.
+    pub fn set_decorations(&mut self, decorations: bool, title: &str) -> Result<(), RootOE> {
+        let holds_canvas = self.frame.as_ref().map_or(false, Frame::holds_canvas);
+        if !decorations {
+            self.frame = None;
+        } else if !holds_canvas {
+            self.frame = None;
+            self.frame = Some(Frame::create(&self.raw, title)?);
+        }
+        Ok(())
+    }
+
+    pub fn frame(&self) -> Option<&Frame> {
+        self.frame.as_ref()
+    }
+
+    // The element laid out in place of the window: the frame while it holds the canvas, or the
+    // canvas itself
+    fn outer_element(&self) -> &HtmlElement {
+        match self.frame {
+            Some(ref frame) if frame.holds_canvas() => frame.raw(),
+            _ => self.raw.as_ref(),
+        }
+    }
+
+    // The position of the border box of the frame, or of the canvas if it has none
+    pub fn outer_position(&self) -> LogicalPosition<f64> {
+        let bounds = self.outer_element().get_bounding_client_rect();
+
+        LogicalPosition {
+            x: bounds.x(),
+            y: bounds.y(),
+        }
+    }
+
+    // The size of the border box of the frame, if it holds the canvas
+    pub fn frame_size(&self) -> Option<LogicalSize<f64>> {
+        match self.frame {
+            Some(ref frame) if frame.holds_canvas() => {
+                let bounds = frame.raw().get_bounding_client_rect();
+                Some(LogicalSize::new(bounds.width(), bounds.height()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn set_outer_style(&self, property: &str, value: &str) {
+        self.outer_element()
+            .style()
+            .set_property(property, value)
+            .unwrap_or_else(|_| panic!("Set style property: {}", property));
+    }
+
+    pub fn set_style(&self, property: &str, value: &str) {
+        self.raw
+            .style()
+            .set_property(property, value)
+            .unwrap_or_else(|_| panic!("Set style property: {}", property));
+    }
+
+    // Shows a canvas created with `hidden_until_ready`, which does nothing if it is shown already
+    pub fn reveal(&self) {
+        let _ = self.raw.style().remove_property("visibility");
+    }
+
+    // Whether the canvas is part of the document and its computed style shows it
+    pub fn is_displayed(&self) -> bool {
+        if !self.raw.is_connected() {
+            return false;
+        }
+        match super::computed_style(&self.raw) {
+            Some(style) => {
+                let display = style.get_property_value("display").unwrap_or_default();
+                let visibility = style.get_property_value("visibility").unwrap_or_default();
+                display != "none" && visibility != "hidden" && visibility != "collapse"
+            }
+            None => true,
+        }
+    }
+
+    // Hides the canvas with `display: none`, which also takes the focus from it. Its own display
+    // value is restored once it is shown again. Returns whether the visibility changed.
+    pub fn set_visible(&self, visible: bool) -> bool {
+        if self.is_visible.replace(visible) == visible {
+            return false;
+        }
+
+        let style = self.raw.style();
+        if visible {
+            let display = self.shown_display.replace(String::new());
+            let _ = if display.is_empty() {
+                style.remove_property("display").map(|_| ())
+            } else {
+                style.set_property("display", &display)
+            };
+        } else {
+            let display = style.get_property_value("display").unwrap_or_default();
+            *self.shown_display.borrow_mut() = display;
+            let _ = style.set_property("display", "none");
+            let _ = self.raw.blur();
+            let _ = self.ime.raw().blur();
+        }
+
+        true
+    }
+
+    // Maximizing pins the frame, or the canvas itself, to the top left corner of the viewport and
+    // sizes the canvas through CSS so that the window fills the viewport, leaving room for the
+    // decorations and the border and padding of the canvas. The resize observer then reports the
+    // new size. The inline styles that are replaced are restored once it is unmaximized.
+    pub fn set_maximized(&self, maximized: bool) {
+        if self.is_maximized() == maximized {
+            return;
+        }
+
+        if !maximized {
+            let styles = self.restored_styles.replace(None).unwrap_or_default();
+            for (element, property, value) in styles {
+                let style = element.style();
+                let _ = if value.is_empty() {
+                    style.remove_property(property).map(|_| ())
+                } else {
+                    style.set_property(property, &value)
+                };
+            }
+            return;
+        }
+
+        let outer = self.outer_element().clone();
+        let canvas: &HtmlElement = &self.raw;
+        let bounds = outer.get_bounding_client_rect();
+        let (width, height) = match super::computed_style(&self.raw) {
+            Some(style) => (
+                super::css_pixels(&style, "width"),
+                super::css_pixels(&style, "height"),
+            ),
+            None => (bounds.width(), bounds.height()),
+        };
+        let width = format!("calc(100vw - {}px)", (bounds.width() - width).max(0.0));
+        let height = format!("calc(100vh - {}px)", (bounds.height() - height).max(0.0));
+
+        let mut styles = Vec::new();
+        for &(element, property, value) in [
+            (&outer, "position", "fixed"),
+            (&outer, "left", "0"),
+            (&outer, "top", "0"),
+            (canvas, "width", width.as_str()),
+            (canvas, "height", height.as_str()),
+        ]
+        .iter()
+        {
+            let style = element.style();
+            let old_value = style.get_property_value(property).unwrap_or_default();
+            let _ = style.set_property(property, value);
+            styles.push((element.clone(), property, old_value));
+        }
+        *self.restored_styles.borrow_mut() = Some(styles);
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.restored_styles.borrow().is_some()
+    }
+
+    // Without a tabindex, the canvas can't be focused and thus receives no keyboard input
+    pub fn set_tab_index(&self, tab_index: Option<i32>) {
+        match tab_index {
+            Some(tab_index) => self.raw.set_tab_index(tab_index),
+            None => {
+                let _ = self.raw.remove_attribute("tabindex");
+                let _ = self.raw.blur();
+                let _ = self.ime.raw().blur();
+            }
+        }
+    }
+
+    // Focusing the canvas moves the focus to its IME element, so the canvas is only focused if
+    // neither is yet, as `Focused(true)` would be emitted again otherwise
+    pub fn focus(&self) {
+        if self.is_visible.get() && !super::is_focused(&self.raw, self.ime.raw()) {
+            let _ = self.raw.focus();
+        }
+    }
+
+    // Disallowing the IME moves the focus from the IME element back to the canvas, which then
+    // receives keyboard input without composition, and allowing it moves the focus there again
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if self.ime_allowed.replace(allowed) == allowed {
+            return;
+        }
+        let ime = self.ime.raw();
+        if super::is_focused(&self.raw, ime) {
+            if allowed {
+                Ime::focus(ime, &self.raw, self.ime_position.get());
+            } else {
+                let _ = self.raw.focus();
+            }
+        }
+    }
+
+    pub fn has_focus(&self) -> bool {
+        super::document_has_focus() && super::is_focused(&self.raw, self.ime.raw())
+    }
+
+    pub fn set_ime_position(&self, position: LogicalPosition<f64>) {
+        self.ime_position.set(position);
+        if self.ime_allowed.get() && super::is_focused(&self.raw, self.ime.raw()) {
+            Ime::place(self.ime.raw(), &self.raw, position);
+        }
+    }
+
+    // Focuses the canvas once the current task has finished, by which time a newly created canvas
+    // has usually been added to the document
+    pub fn focus_later(&self) {
+        let raw = self.raw.clone();
+        super::queue_microtask(move || {
+            let _ = raw.focus();
+        });
+    }
+
+    pub fn is_visible(&self) -> Rc<Cell<bool>> {
+        self.is_visible.clone()
+    }
+
+    pub fn size_constraints(&self) -> Rc<SizeConstraints> {
+        self.size_constraints.clone()
+    }
+
+    pub fn set_min_size(&self, size: Option<Size>) {
+        self.size_constraints.min.set(size);
+        self.apply_size_constraints();
+    }
+
+    pub fn set_max_size(&self, size: Option<Size>) {
+        self.size_constraints.max.set(size);
+        self.apply_size_constraints();
+    }
+
+    // The constraints are also applied to the CSS size, so that the layout of the page respects
+    // them, e.g. when the canvas is sized by a flex container
+    fn apply_size_constraints(&self) {
+        let scale_factor = self.scale_factor();
+        let to_css = |size: Option<Size>| {
+            size.map(|size| size.to_logical::<f64>(scale_factor))
+                .map(|size| (format!("{}px", size.width), format!("{}px", size.height)))
+                .unwrap_or_default()
+        };
+
+        let (min_width, min_height) = to_css(self.size_constraints.min.get());
+        self.set_style("min-width", &min_width);
+        self.set_style("min-height", &min_height);
+
+        let (max_width, max_height) = to_css(self.size_constraints.max.get());
+        self.set_style("max-width", &max_width);
+        self.set_style("max-height", &max_height);
+    }
+
+    // The position of the border box of the canvas, relative to the viewport
     pub fn position(&self) -> LogicalPosition<f64> {
         let bounds = self.raw.get_bounding_client_rect();
 
@@ -104,6 +659,24 @@ impl Canvas {
         }
     }
 
+    // The position of the content box of the canvas, which the drawing buffer is displayed in,
+    // relative to the viewport
+    pub fn content_position(&self) -> LogicalPosition<f64> {
+        let position = self.position();
+        let (padding_left, padding_top) = match super::computed_style(&self.raw) {
+            Some(style) => (
+                super::css_pixels(&style, "padding-left"),
+                super::css_pixels(&style, "padding-top"),
+            ),
+            None => (0.0, 0.0),
+        };
+
+        LogicalPosition {
+            x: position.x + self.raw.client_left() as f64 + padding_left,
+            y: position.y + self.raw.client_top() as f64 + padding_top,
+        }
+    }
+
     pub fn size(&self) -> PhysicalSize<u32> {
         PhysicalSize {
             width: self.raw.width(),
@@ -115,53 +688,147 @@ impl Canvas {
         &self.raw
     }
 
-    pub fn on_blur<F>(&mut self, mut handler: F)
+    // Focus moving between the canvas and its IME element is not reported, as they act as one
+    pub fn on_blur<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(),
     {
-        self.on_blur = Some(self.add_event("blur", None, move |_: FocusEvent| {
-            handler();
+        let handler = Rc::new(RefCell::new(handler));
+
+        let ime = self.ime.raw().clone();
+        let canvas_handler = handler.clone();
+        self.on_blur = Some(self.add_event("blur", None, move |event: FocusEvent| {
+            if !Ime::is(&ime, event.related_target()) {
+                (canvas_handler.borrow_mut())();
+            }
         }));
+
+        let canvas: EventTarget = self.raw.clone().into();
+        self.on_ime_blur = Some(Self::add_event_to(
+            self.ime.raw(),
+            "blur",
+            None,
+            move |event: FocusEvent| {
+                if event.related_target().as_ref() != Some(&canvas) {
+                    (handler.borrow_mut())();
+                }
+            },
+        ));
     }
 
-    pub fn on_focus<F>(&mut self, mut handler: F)
+    pub fn on_focus<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(),
     {
-        self.on_focus = Some(self.add_event("focus", None, move |_: FocusEvent| {
-            handler();
+        let handler = Rc::new(RefCell::new(handler));
+
+        let ime = self.ime.raw().clone();
+        let canvas = self.raw.clone();
+        let canvas_handler = handler.clone();
+        let is_visible = self.is_visible.clone();
+        let ime_allowed = self.ime_allowed.clone();
+        let ime_position = self.ime_position.clone();
+        self.on_focus = Some(self.add_event("focus", None, move |event: FocusEvent| {
+            // A hidden canvas can only be focused programmatically, and only until it is rendered
+            if !is_visible.get() {
+                return;
+            }
+            if !Ime::is(&ime, event.related_target()) {
+                (canvas_handler.borrow_mut())();
+            }
+            // Without the IME, the canvas keeps the focus and receives the keyboard input itself
+            if ime_allowed.get() {
+                Ime::focus(&ime, &canvas, ime_position.get());
+            }
         }));
+
+        let canvas: EventTarget = self.raw.clone().into();
+        self.on_ime_focus = Some(Self::add_event_to(
+            self.ime.raw(),
+            "focus",
+            None,
+            move |event: FocusEvent| {
+                if event.related_target().as_ref() != Some(&canvas) {
+                    (handler.borrow_mut())();
+                }
+            },
+        ));
     }
 
     pub fn on_keyboard_release<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState),
+        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState, String),
     {
+        let prevent_default = self.prevent_default;
+        let is_visible = self.is_visible.clone();
+        let handler = Rc::new(RefCell::new(
+            move |scancode, virtual_keycode, modifiers, key, _| {
+                if is_visible.get() {
+                    handler(scancode, virtual_keycode, modifiers, key);
+                }
+                prevent_default
+            },
+        ));
+        let ime = self.ime.raw().clone();
+
         self.on_keyboard_release =
-            Some(self.add_user_event("keyup", move |event: KeyboardEvent| {
-                handler(
-                    event::scan_code(&event),
-                    event::virtual_key_code(&event),
-                    event::keyboard_modifiers(&event),
-                );
-            }));
+            Some(self.add_user_event("keyup", None, Self::keyboard_handler(handler.clone())));
+        self.on_ime_keyboard_release =
+            Some(self.add_user_event_to(&ime, "keyup", None, Self::keyboard_handler(handler)));
     }
 
     pub fn on_keyboard_press<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState),
+        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState, String),
     {
+        let ignore_key_repeat = self.ignore_key_repeat;
+        let prevented_keys = self.prevented_keys.clone();
+        let prevent_default = self.prevent_default;
+        let is_visible = self.is_visible.clone();
+        let handler = Rc::new(RefCell::new(
+            move |scancode, virtual_keycode, modifiers, key, repeat| {
+                if is_visible.get() && !(repeat && ignore_key_repeat) {
+                    handler(scancode, virtual_keycode, modifiers, key);
+                }
+
+                // Unless all keys are kept from the browser, only the combinations registered by the
+                // user are, so that its other shortcuts keep working.
+                prevent_default
+                    || virtual_keycode
+                        .map_or(false, |key| prevented_keys.contains(&(key, modifiers)))
+            },
+        ));
+        let ime = self.ime.raw().clone();
+
         self.on_keyboard_press =
-            Some(self.add_user_event("keydown", move |event: KeyboardEvent| {
-                handler(
-                    event::scan_code(&event),
-                    event::virtual_key_code(&event),
-                    event::keyboard_modifiers(&event),
-                );
-            }));
+            Some(self.add_user_event("keydown", None, Self::keyboard_handler(handler.clone())));
+        self.on_ime_keyboard_press =
+            Some(self.add_user_event_to(&ime, "keydown", None, Self::keyboard_handler(handler)));
     }
 
-    pub fn on_received_character<F>(&mut self, mut handler: F)
+    // Keyboard events are received by the IME element while it is focused, and by the canvas
+    // otherwise, so the same handler is shared between both. The handler returns whether the
+    // default action of the event should be prevented.
+    fn keyboard_handler<F>(handler: Rc<RefCell<F>>) -> impl FnMut(KeyboardEvent)
+    where
+        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState, String, bool) -> bool,
+    {
+        move |event: KeyboardEvent| {
+            let prevent_default = (handler.borrow_mut())(
+                event::scan_code(&event),
+                event::virtual_key_code(&event),
+                event::keyboard_modifiers(&event),
+                event.key(),
+                event.repeat(),
+            );
+
+            if prevent_default {
+                event.prevent_default();
+            }
+        }
+    }
+
+    pub fn on_received_character<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(char),
     {
@@ -170,16 +837,116 @@ impl Canvas {
         // The `keypress` event is deprecated, but there does not seem to be a
         // viable/compatible alternative as of now. `beforeinput` is still widely
         // unsupported.
+        let handler = Rc::new(RefCell::new(handler));
+        let ime = self.ime.raw().clone();
+
         self.on_received_character = Some(self.add_user_event(
             "keypress",
-            move |event: KeyboardEvent| {
-                // on chrome for the Delete, both key() and code() are "Delete"
-                let key = event.key();
-                let code = event.code();
-                if key != code {
-                    for c in key.chars() {
-                        handler(c);
-                    }
+            None,
+            Self::received_character_handler(handler.clone()),
+        ));
+        let typed = self.ime_typed.clone();
+        let ime_handler = Rc::new(RefCell::new(move |c| {
+            typed.set(true);
+            (handler.borrow_mut())(c);
+        }));
+        self.on_ime_received_character = Some(self.add_user_event_to(
+            &ime,
+            "keypress",
+            None,
+            Self::received_character_handler(ime_handler),
+        ));
+    }
+
+    fn received_character_handler<F>(handler: Rc<RefCell<F>>) -> impl FnMut(KeyboardEvent)
+    where
+        F: 'static + FnMut(char),
+    {
+        move |event: KeyboardEvent| {
+            // Keys pressed during a composition, like the accent of a dead key, only make up the
+            // text that is committed once it ends. On the canvas, which has no composition, the
+            // key following a dead key is reported with the composed character instead.
+            if event.is_composing() || event.key() == "Dead" {
+                return;
+            }
+
+            // on chrome for the Delete, both key() and code() are "Delete"
+            let key = event.key();
+            let code = event.code();
+            if key != code {
+                for c in key.chars() {
+                    (handler.borrow_mut())(c);
+                }
+            }
+        }
+    }
+
+    pub fn on_composition_start<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(String),
+    {
+        self.on_composition_start = Some(Self::add_event_to(
+            self.ime.raw(),
+            "compositionstart",
+            None,
+            move |event: CompositionEvent| {
+                handler(event.data().unwrap_or_default());
+            },
+        ));
+    }
+
+    pub fn on_composition_update<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(String),
+    {
+        self.on_composition_update = Some(Self::add_event_to(
+            self.ime.raw(),
+            "compositionupdate",
+            None,
+            move |event: CompositionEvent| {
+                handler(event.data().unwrap_or_default());
+            },
+        ));
+    }
+
+    pub fn on_composition_end<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(String),
+    {
+        let ime = self.ime.raw().clone();
+        self.on_composition_end = Some(Self::add_event_to(
+            self.ime.raw(),
+            "compositionend",
+            None,
+            move |event: CompositionEvent| {
+                ime.set_value("");
+                handler(event.data().unwrap_or_default());
+            },
+        ));
+    }
+
+    // Text typed outside of a composition is delivered through keyboard events, but text inserted
+    // without any, e.g. from an emoji picker, is only seen in the IME element. The content of the
+    // IME element is discarded after every insertion, to keep it from growing, so that it only
+    // holds the inserted text.
+    pub fn on_inserted_text<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(String),
+    {
+        let ime = self.ime.raw().clone();
+        let typed = self.ime_typed.clone();
+        self.on_ime_input = Some(Self::add_event_to(
+            self.ime.raw(),
+            "input",
+            None,
+            move |event: InputEvent| {
+                if event.is_composing() {
+                    return;
+                }
+                let text = ime.value();
+                ime.set_value("");
+                if !typed.replace(false) && !text.is_empty() {
+                    handler(text);
                 }
             },
         ));
@@ -187,109 +954,690 @@ impl Canvas {
 
     pub fn on_cursor_leave<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(i32),
+        F: 'static + FnMut(i32, PointerType),
     {
-        self.on_cursor_leave = Some(self.add_event("pointerout", None, move |event: PointerEvent| {
-            handler(event.pointer_id());
-        }));
+        self.on_cursor_leave =
+            Some(
+                self.add_event("pointerout", None, move |event: PointerEvent| {
+                    if event::is_touch(&event) {
+                        return;
+                    }
+                    handler(event.pointer_id(), event::pointer_type(&event));
+                }),
+            );
     }
 
     pub fn on_cursor_enter<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(i32),
+        F: 'static + FnMut(i32, PointerType),
     {
-        self.on_cursor_enter = Some(self.add_event("pointerover", None, move |event: PointerEvent| {
-            handler(event.pointer_id());
-        }));
+        self.on_cursor_enter =
+            Some(
+                self.add_event("pointerover", None, move |event: PointerEvent| {
+                    if event::is_touch(&event) {
+                        return;
+                    }
+                    handler(event.pointer_id(), event::pointer_type(&event));
+                }),
+            );
     }
 
     pub fn on_mouse_release<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(i32, MouseButton, ModifiersState),
+        F: 'static + FnMut(i32, PointerType, MouseButton, ModifiersState),
     {
-        self.on_mouse_release = Some(self.add_user_event(
-            "pointerup",
-            move |event: PointerEvent| {
-                handler(
-                    event.pointer_id(),
-                    event::mouse_button(&event),
-                    event::mouse_modifiers(&event),
-                );
-            },
-        ));
+        let canvas = self.raw.clone();
+        let pointer_capture = self.pointer_capture;
+        self.on_mouse_release =
+            Some(
+                self.add_user_event("pointerup", None, move |event: PointerEvent| {
+                    if event::is_touch(&event) {
+                        return;
+                    }
+                    // The capture ends with the last button released, which the browser does as well,
+                    // but only after the event was dispatched
+                    if pointer_capture
+                        && event.buttons() == 0
+                        && canvas.has_pointer_capture(event.pointer_id())
+                    {
+                        let _ = canvas.release_pointer_capture(event.pointer_id());
+                    }
+                    handler(
+                        event.pointer_id(),
+                        event::pointer_type(&event),
+                        event::mouse_button(&event),
+                        event::mouse_modifiers(&event),
+                    );
+                }),
+            );
     }
 
     pub fn on_mouse_press<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(i32, MouseButton, ModifiersState),
+        F: 'static + FnMut(i32, PointerType, MouseButton, ModifiersState),
     {
-        self.on_mouse_press = Some(self.add_user_event(
-            "pointerdown",
-            move |event: PointerEvent| {
-                handler(
-                    event.pointer_id(),
-                    event::mouse_button(&event),
-                    event::mouse_modifiers(&event),
-                );
-            },
-        ));
+        let pointer_down = self.pointer_down.clone();
+        let canvas = self.raw.clone();
+        let pointer_capture = self.pointer_capture;
+        self.on_mouse_press =
+            Some(
+                self.add_user_event("pointerdown", None, move |event: PointerEvent| {
+                    if event::is_touch(&event) {
+                        return;
+                    }
+                    // Captured pointers keep sending their events to the canvas when leaving it, so
+                    // that a drag started on the canvas goes on outside of it
+                    if pointer_capture {
+                        let _ = canvas.set_pointer_capture(event.pointer_id());
+                    }
+                    let previous = pointer_down.replace(Some(event.clone()));
+                    handler(
+                        event.pointer_id(),
+                        event::pointer_type(&event),
+                        event::mouse_button(&event),
+                        event::mouse_modifiers(&event),
+                    );
+                    *pointer_down.borrow_mut() = previous;
+                }),
+            );
     }
 
     pub fn on_cursor_move<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(i32, PhysicalPosition<f64>, ModifiersState),
+        F: 'static
+            + FnMut(i32, PointerType, PhysicalPosition<f64>, PhysicalPosition<f64>, ModifiersState),
     {
-        let mut options = AddEventListenerOptions::new();
-        options.passive(true);
-        self.on_cursor_move = Some(self.add_event("pointermove", Some(options), move |event: PointerEvent| {
-            handler(
-                event.pointer_id(),
-                event::mouse_position(&event).to_physical(super::scale_factor()),
-                event::mouse_modifiers(&event),
+        // Moving the cursor has no default action worth preventing, so the listener is always
+        // passive
+        let options = listener_options(true);
+        let scale_factor_override = self.scale_factor_override.clone();
+        let full_history = self.cursor_move_coalescing == CursorMoveCoalescing::None
+            && event::has_coalesced_events();
+        self.on_cursor_move =
+            Some(
+                self.add_event("pointermove", Some(options), move |event: PointerEvent| {
+                    if event::is_touch(&event) {
+                        return;
+                    }
+                    let scale_factor = scale_factor(&scale_factor_override);
+                    let mut handle = |event: &PointerEvent| {
+                        handler(
+                            event.pointer_id(),
+                            event::pointer_type(event),
+                            event::mouse_position(event).to_physical(scale_factor),
+                            event::mouse_delta(event).to_physical(scale_factor),
+                            event::mouse_modifiers(event),
+                        )
+                    };
+
+                    let coalesced_events = if full_history {
+                        event::coalesced_events(&event)
+                    } else {
+                        Vec::new()
+                    };
+                    if coalesced_events.is_empty() {
+                        handle(&event);
+                    } else {
+                        coalesced_events.iter().for_each(handle);
+                    }
+                }),
             );
-        }));
+    }
+
+    pub fn cursor_move_coalescing(&self) -> CursorMoveCoalescing {
+        self.cursor_move_coalescing
     }
 
     pub fn on_mouse_wheel<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(i32, MouseScrollDelta, ModifiersState),
     {
-        let mut options = AddEventListenerOptions::new();
-        options.passive(true);
-        self.on_mouse_wheel = Some(self.add_event("wheel", Some(options),
-            move |event: WheelEvent| {
-                if let Some(delta) = event::mouse_scroll_delta(&event) {
-                    handler(0, delta, event::mouse_modifiers(&event));
+        // Unless the listeners are passive, scrolling the page is prevented while the canvas is
+        // focused
+        let passive = self.passive_listeners;
+        let prevent_default = self.prevent_default;
+        let options = listener_options(passive);
+        let canvas = self.raw.clone();
+        let ime = self.ime.raw().clone();
+        self.on_mouse_wheel =
+            Some(
+                self.add_event("wheel", Some(options), move |event: WheelEvent| {
+                    if !passive && (prevent_default || super::is_focused(&canvas, &ime)) {
+                        event.prevent_default();
+                    }
+                    if let Some(delta) = event::mouse_scroll_delta(&event) {
+                        handler(0, delta, event::mouse_modifiers(&event));
+                    }
+                }),
+            );
+    }
+
+    pub fn on_touch_start<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        self.on_touch_start = Some(self.add_touch_user_event("touchstart", handler));
+    }
+
+    pub fn on_touch_move<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        self.on_touch_move = Some(self.add_touch_event("touchmove", handler));
+    }
+
+    pub fn on_touch_end<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        self.on_touch_end = Some(self.add_touch_user_event("touchend", handler));
+    }
+
+    pub fn on_touch_cancel<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        self.on_touch_cancel = Some(self.add_touch_event("touchcancel", handler));
+    }
+
+    // Touch events carry every touch point that changed, so the handler is called once per point.
+    fn add_touch_event<F>(
+        &self,
+        event_name: &'static str,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(TouchEvent)>
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        let canvas = self.raw.clone();
+        let scale_factor_override = self.scale_factor_override.clone();
+        let prevent_default = self.prevent_default && !self.passive_listeners;
+        let options = listener_options(self.passive_listeners);
+        self.add_event(event_name, Some(options), move |event: TouchEvent| {
+            if prevent_default {
+                event.prevent_default();
+            }
+            let scale_factor = scale_factor(&scale_factor_override);
+            Self::handle_touches(&canvas, &event, scale_factor, &mut handler);
+        })
+    }
+
+    fn add_touch_user_event<F>(
+        &self,
+        event_name: &'static str,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(TouchEvent)>
+    where
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        let canvas = self.raw.clone();
+        let scale_factor_override = self.scale_factor_override.clone();
+        let prevent_default = self.prevent_default && !self.passive_listeners;
+        let options = listener_options(self.passive_listeners);
+        self.add_user_event(event_name, Some(options), move |event: TouchEvent| {
+            if prevent_default {
+                event.prevent_default();
+            }
+            let scale_factor = scale_factor(&scale_factor_override);
+            Self::handle_touches(&canvas, &event, scale_factor, &mut handler);
+        })
+    }
+
+    // The non-standard gesture events of Safari. The handler receives the scale and the clockwise
+    // rotation in degrees since the start of the gesture.
+    pub fn on_gesture<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(TouchPhase, f64, f64),
+    {
+        let handler = Rc::new(RefCell::new(handler));
+        let gesture_handler = |phase, handler: Rc<RefCell<F>>| {
+            move |event: Event| {
+                let scale = event::gesture_scale(&event);
+                let rotation = event::gesture_rotation(&event);
+                (handler.borrow_mut())(phase, scale, rotation)
+            }
+        };
+
+        self.on_gesture_start = Some(self.add_event(
+            "gesturestart",
+            None,
+            gesture_handler(TouchPhase::Started, handler.clone()),
+        ));
+        self.on_gesture_change = Some(self.add_event(
+            "gesturechange",
+            None,
+            gesture_handler(TouchPhase::Moved, handler.clone()),
+        ));
+        self.on_gesture_end = Some(self.add_event(
+            "gestureend",
+            None,
+            gesture_handler(TouchPhase::Ended, handler),
+        ));
+    }
+
+    // Reports the contacts of pens with the screen, which start when the tip is pressed down.
+    // Their pointer events are reported as mouse events as well.
+    pub fn on_pen<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, TouchPhase, PhysicalPosition<f64>, Force, [f64; 3]),
+    {
+        let handler = Rc::new(RefCell::new(handler));
+        let contacts = Rc::new(RefCell::new(Vec::new()));
+        let scale_factor_override = self.scale_factor_override.clone();
+        let full_history = self.cursor_move_coalescing == CursorMoveCoalescing::None
+            && event::has_coalesced_events();
+        let pen_handler = |phase, handler: Rc<RefCell<F>>| {
+            let contacts = contacts.clone();
+            let scale_factor_override = scale_factor_override.clone();
+            move |event: PointerEvent| {
+                if event::pointer_type(&event) != PointerType::Pen {
+                    return;
+                }
+                let pointer_id = event.pointer_id();
+                {
+                    let mut contacts = contacts.borrow_mut();
+                    let index = contacts.iter().position(|&contact| contact == pointer_id);
+                    match (phase, index) {
+                        // Only the tip starts a contact, not the buttons of the pen
+                        (TouchPhase::Started, None) if event.button() == 0 => {
+                            contacts.push(pointer_id)
+                        }
+                        (TouchPhase::Moved, Some(_)) => (),
+                        (TouchPhase::Ended, Some(index)) | (TouchPhase::Cancelled, Some(index)) => {
+                            contacts.remove(index);
+                        }
+                        _ => return,
+                    }
+                }
+                let scale_factor = scale_factor(&scale_factor_override);
+                let mut handler = handler.borrow_mut();
+                let mut handle = |event: &PointerEvent| {
+                    (handler)(
+                        pointer_id,
+                        phase,
+                        event::mouse_position(event).to_physical(scale_factor),
+                        event::pen_force(event),
+                        event::pen_axes(event),
+                    )
+                };
+
+                // Like the cursor, the contact follows every position of the full history
+                let coalesced_events = if full_history && phase == TouchPhase::Moved {
+                    event::coalesced_events(&event)
+                } else {
+                    Vec::new()
+                };
+                if coalesced_events.is_empty() {
+                    handle(&event);
+                } else {
+                    coalesced_events.iter().for_each(handle);
                 }
             }
+        };
+
+        self.on_pen_down = Some(self.add_event(
+            "pointerdown",
+            None,
+            pen_handler(TouchPhase::Started, handler.clone()),
+        ));
+        self.on_pen_move = Some(self.add_event(
+            "pointermove",
+            Some(listener_options(true)),
+            pen_handler(TouchPhase::Moved, handler.clone()),
+        ));
+        self.on_pen_up = Some(self.add_event(
+            "pointerup",
+            None,
+            pen_handler(TouchPhase::Ended, handler.clone()),
+        ));
+        self.on_pen_cancel = Some(self.add_event(
+            "pointercancel",
+            None,
+            pen_handler(TouchPhase::Cancelled, handler),
         ));
     }
 
-    pub fn on_fullscreen_change<F>(&mut self, mut handler: F)
+    // The handler receives the number of files being dragged, as their names are only available
+    // once they are dropped
+    pub fn on_drag_enter<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(usize),
+    {
+        self.on_drag_enter = Some(self.add_event("dragenter", None, move |event: DragEvent| {
+            event.prevent_default();
+            handler(event::dragged_file_count(&event));
+        }));
+
+        // Dropping is only allowed if `dragover` is cancelled
+        self.on_drag_over = Some(self.add_event("dragover", None, |event: DragEvent| {
+            event.prevent_default();
+        }));
+    }
+
+    pub fn on_drag_leave<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(),
     {
+        self.on_drag_leave = Some(self.add_event("dragleave", None, move |_: DragEvent| {
+            handler();
+        }));
+    }
+
+    // The dropped files replace those of the previous drop, and can be retrieved by name with
+    // `dropped_file` while handling the events
+    pub fn on_drop<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(Vec<String>),
+    {
+        let dropped_files = self.dropped_files.clone();
+        self.on_drop = Some(self.add_event("drop", None, move |event: DragEvent| {
+            // Keep the browser from navigating to the dropped file
+            event.prevent_default();
+
+            let files = event::dropped_files(&event);
+            let names = files.iter().map(File::name).collect();
+            *dropped_files.borrow_mut() = files;
+            handler(names);
+        }));
+    }
+
+    pub fn dropped_file(&self, name: &str) -> Option<File> {
+        self.dropped_files
+            .borrow()
+            .iter()
+            .find(|file| file.name() == name)
+            .cloned()
+    }
+
+    fn handle_touches<F>(
+        canvas: &HtmlCanvasElement,
+        event: &TouchEvent,
+        scale_factor: f64,
+        handler: &mut F,
+    ) where
+        F: FnMut(i32, PhysicalPosition<f64>, Force, Option<PhysicalSize<f64>>),
+    {
+        let touches = event.changed_touches();
+        for index in 0..touches.length() {
+            if let Some(touch) = touches.get(index) {
+                handler(
+                    touch.identifier(),
+                    event::touch_position(&touch, canvas).to_physical(scale_factor),
+                    event::touch_force(&touch),
+                    event::touch_radius(&touch).map(|radius| radius.to_physical(scale_factor)),
+                );
+            }
+        }
+    }
+
+    // The handler receives the scale factor of the canvas
+    pub fn on_fullscreen_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(f64),
+    {
+        let scale_factor_override = self.scale_factor_override.clone();
         self.on_fullscreen_change =
-            Some(self.add_event("fullscreenchange", None, move |_: Event| handler()));
+            Some(self.add_event("fullscreenchange", None, move |_: Event| {
+                handler(scale_factor(&scale_factor_override))
+            }));
     }
 
+    // The handler is called when the user is about to leave the page. If the unload confirmation
+    // is enabled, the browser then asks the user whether to stay on the page.
     pub fn on_before_unload<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(),
     {
-        self.on_before_unload =
-            Some(self.add_window_event("unbeforeunload", move |_: BeforeUnloadEvent| handler()));
+        let confirm_unload = self.confirm_unload.clone();
+        self.on_before_unload = Some(self.add_window_event(
+            "beforeunload",
+            move |event: BeforeUnloadEvent| {
+                handler();
+                if confirm_unload.get() {
+                    // Older browsers only show the dialog if a return value is set
+                    event.prevent_default();
+                    event.set_return_value("");
+                }
+            },
+        ));
+    }
+
+    // The handler is called once the page is actually left, which unlike `beforeunload` can't be
+    // cancelled by the user. It receives whether the page is kept in the back/forward cache, from
+    // which it may be shown again.
+    pub fn on_page_hide<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        self.on_page_hide = Some(
+            self.add_window_event("pagehide", move |event: PageTransitionEvent| {
+                handler(event.persisted())
+            }),
+        );
+    }
+
+    // The handler is called when the page is shown again from the back/forward cache
+    pub fn on_page_show<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_page_show = Some(self.add_window_event(
+            "pageshow",
+            move |event: PageTransitionEvent| {
+                if event.persisted() {
+                    handler();
+                }
+            },
+        ));
     }
 
-    pub fn on_resize<F>(&mut self, mut handler: F)
+    // The Page Lifecycle API freezes hidden pages to save resources, which stops their timers and
+    // animation frames until they are resumed
+    pub fn on_freeze<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(),
     {
-        self.on_resize =
-            Some(self.add_window_event("resize", move |_: UiEvent| handler()));
+        self.on_freeze = Some(self.add_document_event("freeze", move |_: Event| handler()));
+    }
+
+    pub fn on_resume<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_resume = Some(self.add_document_event("resume", move |_: Event| handler()));
+    }
+
+    pub fn set_unload_confirmation(&self, confirm: bool) {
+        self.confirm_unload.set(confirm);
+    }
+
+    // The handler receives the scale factor of the canvas. Some browsers change
+    // `devicePixelRatio` without a resize event, e.g. when moving the window to another monitor,
+    // so the resolution is watched through a media query as well.
+    pub fn on_resize<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(f64),
+    {
+        let handler = Rc::new(RefCell::new(handler));
+
+        *self.on_resolution_change.borrow_mut() = watch_resolution(
+            self.on_resolution_change.clone(),
+            self.scale_factor_override.clone(),
+            handler.clone(),
+        );
+
+        let scale_factor_override = self.scale_factor_override.clone();
+        self.on_resize = Some(self.add_window_event("resize", move |_: UiEvent| {
+            (handler.borrow_mut())(scale_factor(&scale_factor_override))
+        }));
     }
 
-    fn add_event<E, F>(&self, event_name: &str, options: Option<AddEventListenerOptions>, mut handler: F) -> Closure<dyn FnMut(E)>
+    // Scrolling the page or any element containing the canvas moves it in the viewport. Scroll
+    // events of elements don't bubble, so they are caught on their way down to the element instead,
+    // without keeping them from reaching it.
+    pub fn on_scroll<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        let closure = Closure::wrap(Box::new(move |_: Event| handler()) as Box<dyn FnMut(Event)>);
+        self.on_scroll = Some(EventListenerHandle::with_capture(
+            &web_sys::window().expect("Failed to obtain window"),
+            "scroll",
+            closure,
+            listener_options(true),
+        ));
+    }
+
+    // Reports the size the canvas is displayed at, in physical pixels, along with its scale factor,
+    // whenever it changes due to layout. The browser delivers resize observations at most once per
+    // frame, after layout, so they don't need to be debounced further.
+    pub fn on_size_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalSize<u32>, f64),
+    {
+        let scale_factor_override = self.scale_factor_override.clone();
+        let size_constraints = self.size_constraints.clone();
+        let closure = Closure::wrap(Box::new(move |entries: Array| {
+            // Only the canvas is observed, so the last entry holds its latest size
+            if let Some(entry) = entries.iter().last() {
+                let scale_factor_override = scale_factor_override.get();
+                let scale_factor = scale_factor_override.unwrap_or_else(super::scale_factor);
+                let size = event::observed_size(&entry.unchecked_into(), scale_factor_override);
+                handler(size_constraints.clamp(size, scale_factor), scale_factor);
+            }
+        }) as Box<dyn FnMut(Array)>);
+
+        let resize_observer = match ResizeObserver::new(closure.as_ref().unchecked_ref()) {
+            Ok(resize_observer) => resize_observer,
+            Err(_) => {
+                warn!("ResizeObserver is not supported, canvas resizes won't be detected");
+                return;
+            }
+        };
+
+        if event::has_device_pixel_content_box() {
+            let options = ResizeObserverOptions::new();
+            options.set_box(ResizeObserverBoxOptions::DevicePixelContentBox);
+            resize_observer.observe_with_options(&self.raw, &options);
+        } else {
+            resize_observer.observe(&self.raw);
+        }
+
+        if let Some(previous) = self.resize_observer.replace(resize_observer) {
+            previous.disconnect();
+        }
+        self.on_size_change = Some(closure);
+    }
+
+    // The handler receives whether the dark color scheme is now preferred
+    pub fn on_dark_mode<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        let query = match super::dark_mode_query() {
+            Some(query) => query,
+            None => return,
+        };
+
+        let closure =
+            Closure::wrap(
+                Box::new(move |event: MediaQueryListEvent| handler(event.matches()))
+                    as Box<dyn FnMut(MediaQueryListEvent)>,
+            );
+
+        self.on_dark_mode = Some(EventListenerHandle::new(&query, "change", closure));
+    }
+
+    // The handler receives whether the page is now hidden
+    pub fn on_visibility_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        self.on_visibility_change = Some(
+            self.add_document_event("visibilitychange", move |_: Event| {
+                handler(super::is_hidden())
+            }),
+        );
+    }
+
+    pub fn on_context_lost<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_context_lost =
+            Some(
+                self.add_event("webglcontextlost", None, move |event: Event| {
+                    // The context is only ever restored if the default action is prevented
+                    event.prevent_default();
+                    handler();
+                }),
+            );
+    }
+
+    pub fn on_context_restored<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_context_restored =
+            Some(self.add_event("webglcontextrestored", None, move |_: Event| handler()));
+    }
+
+    // The context menu has no corresponding event, so it is only ever prevented. This doesn't
+    // affect the pointer events of the secondary button.
+    pub fn on_context_menu(&mut self) {
+        if self.prevent_default || self.prevent_context_menu {
+            self.on_context_menu =
+                Some(self.add_event("contextmenu", None, |event: Event| event.prevent_default()));
+        }
+    }
+
+    pub fn on_orientation_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(Orientation),
+    {
+        self.on_orientation_change =
+            Some(self.add_window_event("orientationchange", move |_: Event| {
+                if let Some(orientation) = super::orientation() {
+                    handler(orientation);
+                }
+            }));
+    }
+
+    pub fn on_pointer_lock_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_pointer_lock_change =
+            Some(self.add_document_event("pointerlockchange", move |_: Event| handler()));
+    }
+
+    pub fn on_pointer_lock_error<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_pointer_lock_error =
+            Some(self.add_document_event("pointerlockerror", move |_: Event| handler()));
+    }
+
+    fn add_event<E, F>(
+        &self,
+        event_name: &'static str,
+        options: Option<AddEventListenerOptions>,
+        handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
+    where
+        E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
+        F: 'static + FnMut(E),
+    {
+        Self::add_event_to(&self.raw, event_name, options, handler)
+    }
+
+    fn add_event_to<E, F>(
+        target: &EventTarget,
+        event_name: &'static str,
+        options: Option<AddEventListenerOptions>,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
     where
         E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
         F: 'static + FnMut(E),
@@ -303,18 +1651,14 @@ impl Canvas {
             handler(event);
         }) as Box<dyn FnMut(E)>);
 
-        let mut options = options.unwrap_or_default();
-        options.capture(true);
-
-        self.raw.add_event_listener_with_callback_and_add_event_listener_options(
-            event_name, 
-            &closure.as_ref().unchecked_ref(),
-            &options
-        ).expect("Failed to add event listener with callback and listener options");
-        closure
+        EventListenerHandle::with_capture(target, event_name, closure, options.unwrap_or_default())
     }
 
-    fn add_window_event<E, F>(&self, event_name: &str, mut handler: F) -> Closure<dyn FnMut(E)>
+    fn add_window_event<E, F>(
+        &self,
+        event_name: &'static str,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
     where
         E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
         F: 'static + FnMut(E),
@@ -329,42 +1673,169 @@ impl Canvas {
             handler(event);
         }) as Box<dyn FnMut(E)>);
 
-        web_sys::window().unwrap()
-            .add_event_listener_with_callback(event_name, &closure.as_ref().unchecked_ref())
-            .expect("Failed to add event listener with callback");
-
-        closure
+        EventListenerHandle::new(&web_sys::window().unwrap(), event_name, closure)
     }
 
+    // Some events, like pointer lock changes, are only dispatched to the document and never reach
+    // the canvas itself.
+    fn add_document_event<E, F>(
+        &self,
+        event_name: &'static str,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
+    where
+        E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
+        F: 'static + FnMut(E),
+    {
+        let closure = Closure::wrap(Box::new(move |event: E| {
+            handler(event);
+        }) as Box<dyn FnMut(E)>);
+
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .expect("Failed to obtain document");
+
+        EventListenerHandle::new(&document, event_name, closure)
+    }
 
     // The difference between add_event and add_user_event is that the latter has a special meaning
     // for browser security. A user event is a deliberate action by the user (like a mouse or key
     // press) and is the only time things like a fullscreen request may be successfully completed.)
-    fn add_user_event<E, F>(&self, event_name: &str, mut handler: F) -> Closure<dyn FnMut(E)>
+    fn add_user_event<E, F>(
+        &self,
+        event_name: &'static str,
+        options: Option<AddEventListenerOptions>,
+        handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
     where
         E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
         F: 'static + FnMut(E),
     {
-        let wants_fullscreen = self.wants_fullscreen.clone();
-        let canvas = self.raw.clone();
+        self.add_user_event_to(&self.raw, event_name, options, handler)
+    }
 
-        self.add_event(event_name, None, move |event: E| {
-            handler(event);
+    fn add_user_event_to<E, F>(
+        &self,
+        target: &EventTarget,
+        event_name: &'static str,
+        options: Option<AddEventListenerOptions>,
+        mut handler: F,
+    ) -> EventListenerHandle<dyn FnMut(E)>
+    where
+        E: 'static + AsRef<web_sys::Event> + wasm_bindgen::convert::FromWasmAbi,
+        F: 'static + FnMut(E),
+    {
+        let handling_user_event = self.handling_user_event.clone();
 
-            if *wants_fullscreen.borrow() {
-                canvas
-                    .request_fullscreen()
-                    .expect("Failed to enter fullscreen");
-                *wants_fullscreen.borrow_mut() = false;
-            }
+        Self::add_event_to(target, event_name, options, move |event: E| {
+            let was_handling_user_event = handling_user_event.replace(true);
+            handler(event);
+            handling_user_event.set(was_handling_user_event);
         })
     }
 
     pub fn request_fullscreen(&self) {
-        *self.wants_fullscreen.borrow_mut() = true;
+        // Browsers reject fullscreen requests made outside of a user event, so such requests are
+        // not even attempted
+        if !self.handling_user_event.get() {
+            warn!("Fullscreen can only be requested while handling an input event, such as `WindowEvent::MouseInput` or `WindowEvent::KeyboardInput`");
+            return;
+        }
+
+        if let Err(error) = self.raw.request_fullscreen() {
+            warn!("Failed to enter fullscreen: {:?}", error);
+        }
     }
 
     pub fn is_fullscreen(&self) -> bool {
         super::is_fullscreen(&self.raw)
     }
+
+    // Whether a user event is currently being handled, which is required by the browser for
+    // things like clipboard access
+    pub fn user_gesture(&self) -> Rc<dyn Fn() -> bool> {
+        let handling_user_event = self.handling_user_event.clone();
+        Rc::new(move || handling_user_event.get())
+    }
+
+    // Only a pointer that is pressed can be followed, so dragging is only possible while its
+    // `pointerdown` event is handled
+    pub fn drag_window(&self) -> Result<(), RootOE> {
+        match *self.pointer_down.borrow() {
+            Some(ref event) => {
+                self.drag.start(self.outer_element(), event);
+                Ok(())
+            }
+            None => Err(os_error!(OsError(
+                "The window can only be dragged while handling a mouse press".to_owned()
+            ))),
+        }
+    }
+
+    pub fn request_pointer_lock(&self) {
+        self.raw.request_pointer_lock();
+    }
+
+    pub fn is_pointer_locked(&self) -> bool {
+        super::is_pointer_locked(&self.raw)
+    }
+
+    // While the pointer is locked the browser keeps reporting the position at which the lock was
+    // acquired, so the cursor position is accumulated from the movement deltas in here instead.
+    // It is only ever moved by the application otherwise.
+    pub fn locked_cursor_position(&self) -> Rc<Cell<PhysicalPosition<f64>>> {
+        self.locked_cursor_position.clone()
+    }
+
+    // Whether the drawing buffer of the canvas is resized to match its layout, the fullscreen and
+    // the scale factor. Sizes are still reported otherwise, but only the application resizes it.
+    pub fn auto_size(&self) -> Rc<Cell<bool>> {
+        self.auto_size.clone()
+    }
+
+    pub fn set_auto_size(&self, auto_size: bool) {
+        self.auto_size.set(auto_size);
+    }
+}
+
+fn listener_options(passive: bool) -> AddEventListenerOptions {
+    let options = AddEventListenerOptions::new();
+    options.set_passive(passive);
+    options
+}
+
+// The query only matches the current resolution, so it has to be replaced after every change.
+// This happens in a microtask, as the listener can't be dropped while it runs, and only if the
+// listener wasn't removed in the meantime.
+fn watch_resolution<F>(
+    listener: ResolutionListener,
+    scale_factor_override: Rc<Cell<Option<f64>>>,
+    handler: Rc<RefCell<F>>,
+) -> Option<EventListenerHandle<dyn FnMut(MediaQueryListEvent)>>
+where
+    F: 'static + FnMut(f64),
+{
+    let query = super::resolution_query()?;
+
+    let closure = Closure::wrap(Box::new(move |_: MediaQueryListEvent| {
+        (handler.borrow_mut())(scale_factor(&scale_factor_override));
+
+        let listener = listener.clone();
+        let scale_factor_override = scale_factor_override.clone();
+        let handler = handler.clone();
+        super::queue_microtask(move || {
+            if listener.borrow().is_some() {
+                let watcher = watch_resolution(listener.clone(), scale_factor_override, handler);
+                *listener.borrow_mut() = watcher;
+            }
+        });
+    }) as Box<dyn FnMut(MediaQueryListEvent)>);
+
+    Some(EventListenerHandle::new(&query, "change", closure))
+}
+
+fn scale_factor(scale_factor_override: &Cell<Option<f64>>) -> f64 {
+    scale_factor_override
+        .get()
+        .unwrap_or_else(super::scale_factor)
 }
@@ -0,0 +1,42 @@
+use super::on_settled;
+use wasm_bindgen::JsValue;
+
+pub fn read_text<F>(handler: F)
+where
+    F: 'static + FnOnce(Result<String, JsValue>),
+{
+    let clipboard = match clipboard() {
+        Ok(clipboard) => clipboard,
+        Err(error) => return handler(Err(error)),
+    };
+
+    on_settled(&clipboard.read_text(), move |result| {
+        handler(result.map(|text| text.as_string().unwrap_or_default()))
+    });
+}
+
+pub fn write_text<F>(text: &str, handler: F)
+where
+    F: 'static + FnOnce(Result<(), JsValue>),
+{
+    let clipboard = match clipboard() {
+        Ok(clipboard) => clipboard,
+        Err(error) => return handler(Err(error)),
+    };
+
+    on_settled(&clipboard.write_text(text), move |result| {
+        handler(result.map(|_| ()))
+    });
+}
+
+fn clipboard() -> Result<web_sys::Clipboard, JsValue> {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let clipboard = window.navigator().clipboard();
+
+    // The Clipboard API is only exposed in secure contexts
+    if JsValue::from(clipboard.clone()).is_undefined() {
+        Err(JsValue::from_str("The Clipboard API is not available"))
+    } else {
+        Ok(clipboard)
+    }
+}
@@ -0,0 +1,107 @@
+use super::event_handle::EventListenerHandle;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use web_sys::{HtmlElement, PointerEvent};
+
+// Moves an element along with a pointer pressed on a target, until the pointer is released. The
+// pointer is captured by the target, which receives its events even when it leaves the target.
+// The element is moved through its `left` and `top` style properties, away from where it is
+// laid out.
+pub struct Drag {
+    start: DragStart,
+    _on_move: EventListenerHandle<dyn FnMut(PointerEvent)>,
+    _on_end: EventListenerHandle<dyn FnMut(PointerEvent)>,
+    _on_cancel: EventListenerHandle<dyn FnMut(PointerEvent)>,
+}
+
+// Starts a drag from within a `pointerdown` listener of the target
+#[derive(Clone)]
+pub struct DragStart {
+    target: HtmlElement,
+    state: Rc<RefCell<Option<State>>>,
+}
+
+struct State {
+    element: HtmlElement,
+    pointer_id: i32,
+    // The offset of the pointer from the position of the element
+    offset: (f64, f64),
+}
+
+impl Drag {
+    pub fn new(target: &HtmlElement) -> Self {
+        let start = DragStart {
+            target: target.clone(),
+            state: Rc::new(RefCell::new(None)),
+        };
+
+        let state = start.state.clone();
+        let on_move = Closure::wrap(Box::new(move |event: PointerEvent| {
+            if let Some(ref state) = *state.borrow() {
+                if state.pointer_id == event.pointer_id() {
+                    let (x, y) = state.offset;
+                    let style = state.element.style();
+                    let left = f64::from(event.client_x()) - x;
+                    let top = f64::from(event.client_y()) - y;
+                    let _ = style.set_property("left", &format!("{}px", left));
+                    let _ = style.set_property("top", &format!("{}px", top));
+                }
+            }
+        }) as Box<dyn FnMut(PointerEvent)>);
+
+        let end = || {
+            let state = start.state.clone();
+            Closure::wrap(Box::new(move |event: PointerEvent| {
+                let mut state = state.borrow_mut();
+                if state.as_ref().map(|state| state.pointer_id) == Some(event.pointer_id()) {
+                    *state = None;
+                }
+            }) as Box<dyn FnMut(PointerEvent)>)
+        };
+
+        Drag {
+            _on_move: EventListenerHandle::new(target, "pointermove", on_move),
+            _on_end: EventListenerHandle::new(target, "pointerup", end()),
+            _on_cancel: EventListenerHandle::new(target, "pointercancel", end()),
+            start,
+        }
+    }
+
+    pub fn start(&self, element: &HtmlElement, event: &PointerEvent) {
+        self.start.start(element, event)
+    }
+
+    pub fn starter(&self) -> DragStart {
+        self.start.clone()
+    }
+}
+
+impl DragStart {
+    pub fn start(&self, element: &HtmlElement, event: &PointerEvent) {
+        let _ = self.target.set_pointer_capture(event.pointer_id());
+
+        // Statically positioned elements ignore `left` and `top`
+        let style = match super::computed_style(element) {
+            Some(style) => style,
+            None => return,
+        };
+        if style.get_property_value("position").ok().as_deref() == Some("static") {
+            let _ = element.style().set_property("position", "relative");
+        }
+
+        // The computed style is live, so it already reflects the new position
+        let offset = (
+            f64::from(event.client_x()) - super::css_pixels(&style, "left"),
+            f64::from(event.client_y()) - super::css_pixels(&style, "top"),
+        );
+
+        *self.state.borrow_mut() = Some(State {
+            element: element.clone(),
+            pointer_id: event.pointer_id(),
+            offset,
+        });
+    }
+}
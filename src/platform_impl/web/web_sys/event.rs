@@ -1,15 +1,26 @@
-use crate::dpi::LogicalPosition;
-use crate::event::{ModifiersState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode};
+use super::super::keyboard::{self, KeyLocation};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
+use crate::event::{
+    Force, ModifiersState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode,
+};
+use crate::platform::web::PointerType;
 
+use js_sys::{Array, Reflect};
 use std::convert::TryInto;
-use web_sys::{KeyboardEvent, MouseEvent, WheelEvent};
+use wasm_bindgen::JsCast;
+use web_sys::{
+    DragEvent, Event, File, HtmlCanvasElement, KeyboardEvent, MouseEvent, PointerEvent,
+    ResizeObserverEntry, ResizeObserverSize, Touch, WheelEvent,
+};
 
+// The back and forward buttons, `3` and `4` in the DOM, are numbered like `XBUTTON1` and
+// `XBUTTON2` on Windows, and any further buttons follow them
 pub fn mouse_button(event: &MouseEvent) -> MouseButton {
     match event.button() {
         0 => MouseButton::Left,
         1 => MouseButton::Middle,
         2 => MouseButton::Right,
-        i => MouseButton::Other((i - 3).try_into().expect("very large mouse button value")),
+        i => MouseButton::Other((i - 2).try_into().unwrap_or(u8::MAX)),
     }
 }
 
@@ -18,7 +29,10 @@ pub fn mouse_modifiers(event: &MouseEvent) -> ModifiersState {
     m.set(ModifiersState::SHIFT, event.shift_key());
     m.set(ModifiersState::CTRL, event.ctrl_key());
     m.set(ModifiersState::ALT, event.alt_key());
-    m.set(ModifiersState::LOGO, event.meta_key());
+    m.set(
+        ModifiersState::LOGO,
+        event.meta_key() || event.get_modifier_state("OS"),
+    );
     m
 }
 
@@ -29,192 +43,212 @@ pub fn mouse_position(event: &MouseEvent) -> LogicalPosition<f64> {
     }
 }
 
+pub fn mouse_delta(event: &MouseEvent) -> LogicalPosition<f64> {
+    LogicalPosition {
+        x: event.movement_x() as f64,
+        y: event.movement_y() as f64,
+    }
+}
+
+// Touches are reported through `touch*` events, so the pointer events the browser additionally
+// emits for them are ignored to avoid reporting the same input twice.
+pub fn is_touch(event: &PointerEvent) -> bool {
+    event.pointer_type() == "touch"
+}
+
+pub fn pointer_type(event: &PointerEvent) -> PointerType {
+    match event.pointer_type().as_str() {
+        "pen" => PointerType::Pen,
+        "touch" => PointerType::Touch,
+        _ => PointerType::Mouse,
+    }
+}
+
+// Browsers normalize the pressure of pens, and report their tilt along both axes of the screen,
+// from which the angle between the pen and the screen is derived
+pub fn pen_force(event: &PointerEvent) -> Force {
+    let tilt_x = (event.tilt_x() as f64).to_radians().tan();
+    let tilt_y = (event.tilt_y() as f64).to_radians().tan();
+    let altitude_angle = 1f64.atan2((tilt_x * tilt_x + tilt_y * tilt_y).sqrt());
+    Force::Calibrated {
+        force: event.pressure() as f64,
+        max_possible_force: 1.0,
+        altitude_angle: Some(altitude_angle),
+    }
+}
+
+// The pressure, from 0 to 1, and the tilt along the x and y axes, from -90 to 90 degrees
+pub fn pen_axes(event: &PointerEvent) -> [f64; 3] {
+    [
+        event.pressure() as f64,
+        event.tilt_x() as f64,
+        event.tilt_y() as f64,
+    ]
+}
+
+// `getCoalescedEvents()` isn't supported by every browser, and calling it would throw
+pub fn has_coalesced_events() -> bool {
+    let global = js_sys::global();
+    Reflect::get(&global, &"PointerEvent".into())
+        .and_then(|event| Reflect::get(&event, &"prototype".into()))
+        .and_then(|prototype| Reflect::has(&prototype, &"getCoalescedEvents".into()))
+        .unwrap_or(false)
+}
+
+pub fn coalesced_events(event: &PointerEvent) -> Vec<PointerEvent> {
+    event
+        .get_coalesced_events()
+        .iter()
+        .map(JsCast::unchecked_into)
+        .collect()
+}
+
+pub fn touch_position(touch: &Touch, canvas: &HtmlCanvasElement) -> LogicalPosition<f64> {
+    let bounds = canvas.get_bounding_client_rect();
+
+    LogicalPosition {
+        x: touch.client_x() as f64 - bounds.x(),
+        y: touch.client_y() as f64 - bounds.y(),
+    }
+}
+
+// Browsers without pressure support always report a force of 0, which can't be told apart from a
+// touch that is barely pressed
+pub fn touch_force(touch: &Touch) -> Force {
+    Force::Normalized(touch.force() as f64)
+}
+
+// Browsers without support for the area of contact report radii of 0, or of 1 on some touchscreens
+pub fn touch_radius(touch: &Touch) -> Option<LogicalSize<f64>> {
+    match (touch.radius_x(), touch.radius_y()) {
+        (x, y) if x > 1 || y > 1 => Some(LogicalSize::new(x as f64, y as f64)),
+        _ => None,
+    }
+}
+
 pub fn mouse_scroll_delta(event: &WheelEvent) -> Option<MouseScrollDelta> {
     let x = event.delta_x();
     let y = event.delta_y();
 
     match event.delta_mode() {
-        WheelEvent::DOM_DELTA_LINE => Some(MouseScrollDelta::LineDelta(x as f32, y as f32)),
+        // There is no page delta, so scrolling by pages is reported as the number of pages, which
+        // is still a discrete unit like lines
+        WheelEvent::DOM_DELTA_LINE | WheelEvent::DOM_DELTA_PAGE => {
+            Some(MouseScrollDelta::LineDelta(x as f32, y as f32))
+        }
+        // Pixel deltas are in CSS pixels, which are logical pixels
         WheelEvent::DOM_DELTA_PIXEL => Some(MouseScrollDelta::PixelDelta(LogicalPosition { x, y })),
         _ => None,
     }
 }
 
+// The code, key and location of the event are mapped by the `keyboard` module, which doesn't
+// depend on web-sys, so it is tested on the host
 pub fn scan_code(event: &KeyboardEvent) -> ScanCode {
-    match event.key_code() {
-        0 => event.char_code(),
-        i => i,
-    }
+    keyboard::scan_code(&event.code(), &event.key(), key_location(event))
 }
 
 pub fn virtual_key_code(event: &KeyboardEvent) -> Option<VirtualKeyCode> {
-    Some(match &event.code()[..] {
-        "Digit1" => VirtualKeyCode::Key1,
-        "Digit2" => VirtualKeyCode::Key2,
-        "Digit3" => VirtualKeyCode::Key3,
-        "Digit4" => VirtualKeyCode::Key4,
-        "Digit5" => VirtualKeyCode::Key5,
-        "Digit6" => VirtualKeyCode::Key6,
-        "Digit7" => VirtualKeyCode::Key7,
-        "Digit8" => VirtualKeyCode::Key8,
-        "Digit9" => VirtualKeyCode::Key9,
-        "Digit0" => VirtualKeyCode::Key0,
-        "KeyA" => VirtualKeyCode::A,
-        "KeyB" => VirtualKeyCode::B,
-        "KeyC" => VirtualKeyCode::C,
-        "KeyD" => VirtualKeyCode::D,
-        "KeyE" => VirtualKeyCode::E,
-        "KeyF" => VirtualKeyCode::F,
-        "KeyG" => VirtualKeyCode::G,
-        "KeyH" => VirtualKeyCode::H,
-        "KeyI" => VirtualKeyCode::I,
-        "KeyJ" => VirtualKeyCode::J,
-        "KeyK" => VirtualKeyCode::K,
-        "KeyL" => VirtualKeyCode::L,
-        "KeyM" => VirtualKeyCode::M,
-        "KeyN" => VirtualKeyCode::N,
-        "KeyO" => VirtualKeyCode::O,
-        "KeyP" => VirtualKeyCode::P,
-        "KeyQ" => VirtualKeyCode::Q,
-        "KeyR" => VirtualKeyCode::R,
-        "KeyS" => VirtualKeyCode::S,
-        "KeyT" => VirtualKeyCode::T,
-        "KeyU" => VirtualKeyCode::U,
-        "KeyV" => VirtualKeyCode::V,
-        "KeyW" => VirtualKeyCode::W,
-        "KeyX" => VirtualKeyCode::X,
-        "KeyY" => VirtualKeyCode::Y,
-        "KeyZ" => VirtualKeyCode::Z,
-        "Escape" => VirtualKeyCode::Escape,
-        "F1" => VirtualKeyCode::F1,
-        "F2" => VirtualKeyCode::F2,
-        "F3" => VirtualKeyCode::F3,
-        "F4" => VirtualKeyCode::F4,
-        "F5" => VirtualKeyCode::F5,
-        "F6" => VirtualKeyCode::F6,
-        "F7" => VirtualKeyCode::F7,
-        "F8" => VirtualKeyCode::F8,
-        "F9" => VirtualKeyCode::F9,
-        "F10" => VirtualKeyCode::F10,
-        "F11" => VirtualKeyCode::F11,
-        "F12" => VirtualKeyCode::F12,
-        "F13" => VirtualKeyCode::F13,
-        "F14" => VirtualKeyCode::F14,
-        "F15" => VirtualKeyCode::F15,
-        "F16" => VirtualKeyCode::F16,
-        "F17" => VirtualKeyCode::F17,
-        "F18" => VirtualKeyCode::F18,
-        "F19" => VirtualKeyCode::F19,
-        "F20" => VirtualKeyCode::F20,
-        "F21" => VirtualKeyCode::F21,
-        "F22" => VirtualKeyCode::F22,
-        "F23" => VirtualKeyCode::F23,
-        "F24" => VirtualKeyCode::F24,
-        "PrintScreen" => VirtualKeyCode::Snapshot,
-        "ScrollLock" => VirtualKeyCode::Scroll,
-        "Pause" => VirtualKeyCode::Pause,
-        "Insert" => VirtualKeyCode::Insert,
-        "Home" => VirtualKeyCode::Home,
-        "Delete" => VirtualKeyCode::Delete,
-        "End" => VirtualKeyCode::End,
-        "PageDown" => VirtualKeyCode::PageDown,
-        "PageUp" => VirtualKeyCode::PageUp,
-        "ArrowLeft" => VirtualKeyCode::Left,
-        "ArrowUp" => VirtualKeyCode::Up,
-        "ArrowRight" => VirtualKeyCode::Right,
-        "ArrowDown" => VirtualKeyCode::Down,
-        "Backspace" => VirtualKeyCode::Back,
-        "Enter" => VirtualKeyCode::Return,
-        "Space" => VirtualKeyCode::Space,
-        "Compose" => VirtualKeyCode::Compose,
-        "Caret" => VirtualKeyCode::Caret,
-        "NumLock" => VirtualKeyCode::Numlock,
-        "Numpad0" => VirtualKeyCode::Numpad0,
-        "Numpad1" => VirtualKeyCode::Numpad1,
-        "Numpad2" => VirtualKeyCode::Numpad2,
-        "Numpad3" => VirtualKeyCode::Numpad3,
-        "Numpad4" => VirtualKeyCode::Numpad4,
-        "Numpad5" => VirtualKeyCode::Numpad5,
-        "Numpad6" => VirtualKeyCode::Numpad6,
-        "Numpad7" => VirtualKeyCode::Numpad7,
-        "Numpad8" => VirtualKeyCode::Numpad8,
-        "Numpad9" => VirtualKeyCode::Numpad9,
-        "AbntC1" => VirtualKeyCode::AbntC1,
-        "AbntC2" => VirtualKeyCode::AbntC2,
-        "NumpadAdd" => VirtualKeyCode::Add,
-        "Quote" => VirtualKeyCode::Apostrophe,
-        "Apps" => VirtualKeyCode::Apps,
-        "At" => VirtualKeyCode::At,
-        "Ax" => VirtualKeyCode::Ax,
-        "Backslash" => VirtualKeyCode::Backslash,
-        "Calculator" => VirtualKeyCode::Calculator,
-        "Capital" => VirtualKeyCode::Capital,
-        "Semicolon" => VirtualKeyCode::Semicolon,
-        "Comma" => VirtualKeyCode::Comma,
-        "Convert" => VirtualKeyCode::Convert,
-        "NumpadDecimal" => VirtualKeyCode::Decimal,
-        "NumpadDivide" => VirtualKeyCode::Divide,
-        "Equal" => VirtualKeyCode::Equals,
-        "Backquote" => VirtualKeyCode::Grave,
-        "Kana" => VirtualKeyCode::Kana,
-        "Kanji" => VirtualKeyCode::Kanji,
-        "AltLeft" => VirtualKeyCode::LAlt,
-        "BracketLeft" => VirtualKeyCode::LBracket,
-        "ControlLeft" => VirtualKeyCode::LControl,
-        "ShiftLeft" => VirtualKeyCode::LShift,
-        "MetaLeft" => VirtualKeyCode::LWin,
-        "Mail" => VirtualKeyCode::Mail,
-        "MediaSelect" => VirtualKeyCode::MediaSelect,
-        "MediaStop" => VirtualKeyCode::MediaStop,
-        "Minus" => VirtualKeyCode::Minus,
-        "NumpadMultiply" => VirtualKeyCode::Multiply,
-        "Mute" => VirtualKeyCode::Mute,
-        "LaunchMyComputer" => VirtualKeyCode::MyComputer,
-        "NavigateForward" => VirtualKeyCode::NavigateForward,
-        "NavigateBackward" => VirtualKeyCode::NavigateBackward,
-        "NextTrack" => VirtualKeyCode::NextTrack,
-        "NoConvert" => VirtualKeyCode::NoConvert,
-        "NumpadComma" => VirtualKeyCode::NumpadComma,
-        "NumpadEnter" => VirtualKeyCode::NumpadEnter,
-        "NumpadEquals" => VirtualKeyCode::NumpadEquals,
-        "OEM102" => VirtualKeyCode::OEM102,
-        "Period" => VirtualKeyCode::Period,
-        "PlayPause" => VirtualKeyCode::PlayPause,
-        "Power" => VirtualKeyCode::Power,
-        "PrevTrack" => VirtualKeyCode::PrevTrack,
-        "AltRight" => VirtualKeyCode::RAlt,
-        "BracketRight" => VirtualKeyCode::RBracket,
-        "ControlRight" => VirtualKeyCode::RControl,
-        "ShiftRight" => VirtualKeyCode::RShift,
-        "MetaRight" => VirtualKeyCode::RWin,
-        "Slash" => VirtualKeyCode::Slash,
-        "Sleep" => VirtualKeyCode::Sleep,
-        "Stop" => VirtualKeyCode::Stop,
-        "NumpadSubtract" => VirtualKeyCode::Subtract,
-        "Sysrq" => VirtualKeyCode::Sysrq,
-        "Tab" => VirtualKeyCode::Tab,
-        "Underline" => VirtualKeyCode::Underline,
-        "Unlabeled" => VirtualKeyCode::Unlabeled,
-        "AudioVolumeDown" => VirtualKeyCode::VolumeDown,
-        "AudioVolumeUp" => VirtualKeyCode::VolumeUp,
-        "Wake" => VirtualKeyCode::Wake,
-        "WebBack" => VirtualKeyCode::WebBack,
-        "WebFavorites" => VirtualKeyCode::WebFavorites,
-        "WebForward" => VirtualKeyCode::WebForward,
-        "WebHome" => VirtualKeyCode::WebHome,
-        "WebRefresh" => VirtualKeyCode::WebRefresh,
-        "WebSearch" => VirtualKeyCode::WebSearch,
-        "WebStop" => VirtualKeyCode::WebStop,
-        "Yen" => VirtualKeyCode::Yen,
-        _ => return None,
-    })
+    keyboard::virtual_key_code(&event.code(), &event.key(), key_location(event))
+}
+
+fn key_location(event: &KeyboardEvent) -> KeyLocation {
+    match event.location() {
+        KeyboardEvent::DOM_KEY_LOCATION_LEFT => KeyLocation::Left,
+        KeyboardEvent::DOM_KEY_LOCATION_RIGHT => KeyLocation::Right,
+        KeyboardEvent::DOM_KEY_LOCATION_NUMPAD => KeyLocation::Numpad,
+        _ => KeyLocation::Standard,
+    }
 }
 
+// Older versions of Firefox report the Windows and Super keys as the "OS" modifier rather than
+// through `metaKey`, here as well as in `mouse_modifiers`
 pub fn keyboard_modifiers(event: &KeyboardEvent) -> ModifiersState {
     let mut m = ModifiersState::empty();
     m.set(ModifiersState::SHIFT, event.shift_key());
     m.set(ModifiersState::CTRL, event.ctrl_key());
     m.set(ModifiersState::ALT, event.alt_key());
-    m.set(ModifiersState::LOGO, event.meta_key());
+    m.set(
+        ModifiersState::LOGO,
+        event.meta_key() || event.get_modifier_state("OS"),
+    );
     m
 }
+
+// `GestureEvent` is only implemented by Safari, so its properties are read dynamically
+pub fn gesture_scale(event: &Event) -> f64 {
+    Reflect::get(event, &"scale".into())
+        .ok()
+        .and_then(|scale| scale.as_f64())
+        .unwrap_or(1.0)
+}
+
+pub fn gesture_rotation(event: &Event) -> f64 {
+    Reflect::get(event, &"rotation".into())
+        .ok()
+        .and_then(|rotation| rotation.as_f64())
+        .unwrap_or(0.0)
+}
+
+pub fn dragged_file_count(event: &DragEvent) -> usize {
+    let items = match event.data_transfer() {
+        Some(data_transfer) => data_transfer.items(),
+        None => return 0,
+    };
+
+    (0..items.length())
+        .filter_map(|index| items.get(index))
+        .filter(|item| item.kind() == "file")
+        .count()
+}
+
+pub fn dropped_files(event: &DragEvent) -> Vec<File> {
+    let files = match event
+        .data_transfer()
+        .and_then(|data_transfer| data_transfer.files())
+    {
+        Some(files) => files,
+        None => return Vec::new(),
+    };
+
+    (0..files.length())
+        .filter_map(|index| files.get(index))
+        .collect()
+}
+
+// `devicePixelContentBoxSize` gives the exact size in device pixels, which can't be derived from
+// the CSS size as browsers snap the content box to the pixel grid
+pub fn has_device_pixel_content_box() -> bool {
+    let global = js_sys::global();
+    Reflect::get(&global, &"ResizeObserverEntry".into())
+        .and_then(|entry| Reflect::get(&entry, &"prototype".into()))
+        .and_then(|prototype| Reflect::has(&prototype, &"devicePixelContentBoxSize".into()))
+        .unwrap_or(false)
+}
+
+// With a scale factor override, the size is derived from the CSS size using the override, as the
+// device pixel size is irrelevant
+pub fn observed_size(
+    entry: &ResizeObserverEntry,
+    scale_factor_override: Option<f64>,
+) -> PhysicalSize<u32> {
+    if scale_factor_override.is_none() && has_device_pixel_content_box() {
+        if let Some(size) = first_size(&entry.device_pixel_content_box_size()) {
+            return PhysicalSize {
+                width: size.inline_size().round() as u32,
+                height: size.block_size().round() as u32,
+            };
+        }
+    }
+
+    let rect = entry.content_rect();
+    LogicalSize {
+        width: rect.width(),
+        height: rect.height(),
+    }
+    .to_physical(scale_factor_override.unwrap_or_else(super::scale_factor))
+}
+
+fn first_size(sizes: &Array) -> Option<ResizeObserverSize> {
+    sizes.iter().next().map(JsCast::unchecked_into)
+}
@@ -0,0 +1,64 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{AddEventListenerOptions, EventTarget};
+
+// An event listener that is removed from its target when dropped, so that the browser never calls
+// into a closure that has been freed
+pub struct EventListenerHandle<T: ?Sized> {
+    target: EventTarget,
+    event_type: &'static str,
+    capture: bool,
+    listener: Closure<T>,
+}
+
+impl<T: ?Sized> EventListenerHandle<T> {
+    pub fn new(target: &EventTarget, event_type: &'static str, listener: Closure<T>) -> Self {
+        target
+            .add_event_listener_with_callback(event_type, listener.as_ref().unchecked_ref())
+            .expect("Failed to add event listener with callback");
+
+        EventListenerHandle {
+            target: target.clone(),
+            event_type,
+            capture: false,
+            listener,
+        }
+    }
+
+    pub fn with_capture(
+        target: &EventTarget,
+        event_type: &'static str,
+        listener: Closure<T>,
+        options: AddEventListenerOptions,
+    ) -> Self {
+        options.set_capture(true);
+
+        target
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                event_type,
+                listener.as_ref().unchecked_ref(),
+                &options,
+            )
+            .expect("Failed to add event listener with callback and listener options");
+
+        EventListenerHandle {
+            target: target.clone(),
+            event_type,
+            capture: true,
+            listener,
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for EventListenerHandle<T> {
+    fn drop(&mut self) {
+        self.target
+            .remove_event_listener_with_callback_and_bool(
+                self.event_type,
+                self.listener.as_ref().unchecked_ref(),
+                self.capture,
+            )
+            .unwrap_or_else(|e| {
+                web_sys::console::error_2(&"Error removing event listener".into(), &e)
+            });
+    }
+}
@@ -0,0 +1,137 @@
+use super::drag::Drag;
+use super::event_handle::EventListenerHandle;
+use crate::error::OsError as RootOE;
+use crate::platform_impl::OsError;
+
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{HtmlCanvasElement, HtmlElement, PointerEvent};
+
+// There are no native decorations on the web, so they are drawn by a container that takes the place
+// of the canvas in the document, with a title bar above it. Dragging the title bar moves the
+// container away from where it is laid out. The container and the title bar can be styled through
+// their `winit-frame` and `winit-title-bar` classes.
+pub struct Frame {
+    raw: HtmlElement,
+    title_bar: HtmlElement,
+    canvas: HtmlCanvasElement,
+    _on_drag_start: EventListenerHandle<dyn FnMut(PointerEvent)>,
+    _drag: Drag,
+}
+
+// If the canvas is still in the frame, it is put back in the place of the frame
+impl Drop for Frame {
+    fn drop(&mut self) {
+        let canvas: &web_sys::Node = self.canvas.as_ref();
+        let raw: &web_sys::Node = self.raw.as_ref();
+        if self.holds_canvas() {
+            match self.raw.parent_node() {
+                Some(parent) => {
+                    let _ = parent.replace_child(canvas, raw);
+                }
+                None => {
+                    let _ = raw.remove_child(canvas);
+                }
+            }
+        }
+        self.raw.remove();
+    }
+}
+
+impl Frame {
+    pub fn create(canvas: &HtmlCanvasElement, title: &str) -> Result<Self, RootOE> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or(os_error!(OsError("Failed to obtain document".to_owned())))?;
+
+        let create_div = || -> Result<HtmlElement, RootOE> {
+            Ok(document
+                .create_element("div")
+                .map_err(|_| os_error!(OsError("Failed to create frame element".to_owned())))?
+                .unchecked_into())
+        };
+        let raw = create_div()?;
+        let title_bar = create_div()?;
+
+        raw.set_class_name("winit-frame");
+        title_bar.set_class_name("winit-title-bar");
+        title_bar.set_text_content(Some(title));
+
+        for (element, styles) in &[
+            (
+                &raw,
+                &[
+                    ("display", "inline-block"),
+                    ("position", "relative"),
+                    ("border", "1px solid #808080"),
+                    ("line-height", "0"),
+                    ("vertical-align", "top"),
+                ][..],
+            ),
+            (
+                &title_bar,
+                &[
+                    ("padding", "4px 8px"),
+                    ("background", "#e0e0e0"),
+                    ("color", "#202020"),
+                    ("font", "13px sans-serif"),
+                    ("line-height", "16px"),
+                    ("white-space", "nowrap"),
+                    ("overflow", "hidden"),
+                    ("cursor", "move"),
+                    ("user-select", "none"),
+                    ("touch-action", "none"),
+                ][..],
+            ),
+        ] {
+            for (property, value) in styles.iter() {
+                element
+                    .style()
+                    .set_property(property, value)
+                    .map_err(|_| os_error!(OsError("Failed to style frame element".to_owned())))?;
+            }
+        }
+
+        let drag = Drag::new(&title_bar);
+        let on_drag_start = {
+            let raw = raw.clone();
+            let start = drag.starter();
+            Closure::wrap(Box::new(move |event: PointerEvent| {
+                if event.button() == 0 {
+                    start.start(&raw, &event);
+                    event.prevent_default();
+                }
+            }) as Box<dyn FnMut(PointerEvent)>)
+        };
+
+        let frame = Frame {
+            _on_drag_start: EventListenerHandle::new(&title_bar, "pointerdown", on_drag_start),
+            _drag: drag,
+            raw,
+            title_bar,
+            canvas: canvas.clone(),
+        };
+
+        if let Some(parent) = canvas.parent_node() {
+            let _ = parent.replace_child(&frame.raw, canvas);
+        }
+        let _ = frame.raw.append_child(&frame.title_bar);
+        let _ = frame.raw.append_child(canvas);
+
+        Ok(frame)
+    }
+
+    pub fn raw(&self) -> &HtmlElement {
+        &self.raw
+    }
+
+    // The canvas can be moved out of the frame, e.g. by appending it to the document, which leaves
+    // the frame empty
+    pub fn holds_canvas(&self) -> bool {
+        let raw: &web_sys::Node = self.raw.as_ref();
+        self.canvas.parent_node().as_ref() == Some(raw)
+    }
+
+    pub fn set_title(&self, title: &str) {
+        self.title_bar.set_text_content(Some(title));
+    }
+}
@@ -0,0 +1,161 @@
+use super::event_handle::EventListenerHandle;
+use crate::event::{DeviceEvent, ElementState};
+
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Gamepad, GamepadButton, GamepadEvent};
+
+// Browsers don't emit events for gamepad input, so the state of every gamepad is sampled once per
+// animation frame and compared with the previous sample.
+pub struct Gamepads {
+    states: Vec<Option<State>>,
+    _on_connected: EventListenerHandle<dyn FnMut(GamepadEvent)>,
+    _on_disconnected: EventListenerHandle<dyn FnMut(GamepadEvent)>,
+}
+
+#[derive(Default)]
+struct State {
+    buttons: Vec<bool>,
+    axes: Vec<f64>,
+}
+
+impl Gamepads {
+    // The handler is called whenever a gamepad is connected or disconnected, to resume sampling.
+    //
+    // Gamepads are only exposed to the page once a button has been pressed on them, at which point
+    // the browser emits `gamepadconnected`.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: 'static + Fn(),
+    {
+        let handler = Rc::new(handler);
+
+        let connected_handler = handler.clone();
+        let on_connected = add_window_event("gamepadconnected", move |_| connected_handler());
+        let on_disconnected = add_window_event("gamepaddisconnected", move |_| handler());
+
+        Gamepads {
+            states: Vec::new(),
+            _on_connected: on_connected,
+            _on_disconnected: on_disconnected,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.states.iter().any(Option::is_some)
+    }
+
+    // Samples all gamepads, returning the index of the gamepad each event belongs to
+    pub fn poll(&mut self) -> Vec<(u32, DeviceEvent)> {
+        let mut events = Vec::new();
+        let gamepads = gamepads();
+
+        let len = gamepads.len().max(self.states.len());
+        self.states.resize_with(len, Default::default);
+
+        for (index, state) in self.states.iter_mut().enumerate() {
+            let index = index as u32;
+            let gamepad = gamepads.get(index as usize).cloned().flatten();
+
+            match (state.as_mut(), gamepad) {
+                (None, Some(gamepad)) => {
+                    events.push((index, DeviceEvent::Added));
+                    let mut new = State::default();
+                    new.update(index, &gamepad, &mut events);
+                    *state = Some(new);
+                }
+                (Some(previous), Some(gamepad)) => previous.update(index, &gamepad, &mut events),
+                (Some(_), None) => {
+                    *state = None;
+                    events.push((index, DeviceEvent::Removed));
+                }
+                (None, None) => (),
+            }
+        }
+
+        events
+    }
+}
+
+impl State {
+    fn update(&mut self, index: u32, gamepad: &Gamepad, events: &mut Vec<(u32, DeviceEvent)>) {
+        let buttons: Vec<bool> = gamepad
+            .buttons()
+            .iter()
+            .map(|button| button.unchecked_into::<GamepadButton>().pressed())
+            .collect();
+        self.buttons.resize(buttons.len(), false);
+
+        for (button, (&pressed, previous)) in buttons.iter().zip(&mut self.buttons).enumerate() {
+            if pressed != *previous {
+                *previous = pressed;
+                events.push((
+                    index,
+                    DeviceEvent::Button {
+                        button: button as u32,
+                        state: if pressed {
+                            ElementState::Pressed
+                        } else {
+                            ElementState::Released
+                        },
+                    },
+                ));
+            }
+        }
+
+        let axes: Vec<f64> = gamepad
+            .axes()
+            .iter()
+            .map(|axis| axis.as_f64().unwrap_or(0.0))
+            .collect();
+        self.axes.resize(axes.len(), 0.0);
+
+        for (axis, (&value, previous)) in axes.iter().zip(&mut self.axes).enumerate() {
+            #[allow(clippy::float_cmp)]
+            let changed = value != *previous;
+            if changed {
+                *previous = value;
+                events.push((
+                    index,
+                    DeviceEvent::Motion {
+                        axis: axis as u32,
+                        value,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+fn gamepads() -> Vec<Option<Gamepad>> {
+    let navigator = web_sys::window()
+        .expect("Failed to obtain window")
+        .navigator();
+
+    match navigator.get_gamepads() {
+        Ok(gamepads) => gamepads
+            .iter()
+            .map(|gamepad| {
+                gamepad
+                    .dyn_into::<Gamepad>()
+                    .ok()
+                    .filter(Gamepad::connected)
+            })
+            .collect(),
+        // Gamepads can be unavailable, e.g. in insecure contexts
+        Err(_) => Vec::new(),
+    }
+}
+
+fn add_window_event<F>(
+    event_name: &'static str,
+    handler: F,
+) -> EventListenerHandle<dyn FnMut(GamepadEvent)>
+where
+    F: 'static + FnMut(GamepadEvent),
+{
+    let closure = Closure::wrap(Box::new(handler) as Box<dyn FnMut(GamepadEvent)>);
+    let window = web_sys::window().expect("Failed to obtain window");
+
+    EventListenerHandle::new(&window, event_name, closure)
+}
@@ -0,0 +1,104 @@
+use crate::dpi::LogicalPosition;
+use crate::error::OsError as RootOE;
+use crate::platform_impl::OsError;
+
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlCanvasElement, HtmlInputElement, Node};
+
+// Canvas elements can't receive text input, so composition is done in a hidden input element that
+// is kept focused, and placed over the canvas, whenever the canvas is focused. This also lets the
+// browser position the IME candidate window next to the canvas.
+pub struct Ime {
+    raw: HtmlInputElement,
+}
+
+impl Drop for Ime {
+    fn drop(&mut self) {
+        self.raw.remove();
+    }
+}
+
+impl Ime {
+    pub fn create() -> Result<Self, RootOE> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or(os_error!(OsError("Failed to obtain document".to_owned())))?;
+
+        let raw: HtmlInputElement = document
+            .create_element("input")
+            .map_err(|_| os_error!(OsError("Failed to create IME element".to_owned())))?
+            .unchecked_into();
+
+        raw.set_type("text");
+        // The IME element must not be reachable through sequential keyboard navigation, as the
+        // canvas already is
+        raw.set_tab_index(-1);
+        raw.set_attribute("autocomplete", "off")
+            .map_err(|_| os_error!(OsError("Failed to set autocomplete".to_owned())))?;
+        raw.set_attribute("aria-hidden", "true")
+            .map_err(|_| os_error!(OsError("Failed to set aria-hidden".to_owned())))?;
+
+        let style = raw.style();
+        for (property, value) in &[
+            ("position", "fixed"),
+            ("opacity", "0"),
+            ("width", "1px"),
+            ("height", "1px"),
+            ("padding", "0"),
+            ("border", "0"),
+            ("pointer-events", "none"),
+        ] {
+            style
+                .set_property(property, value)
+                .map_err(|_| os_error!(OsError("Failed to style IME element".to_owned())))?;
+        }
+
+        Ok(Ime { raw })
+    }
+
+    pub fn raw(&self) -> &HtmlInputElement {
+        &self.raw
+    }
+
+    pub fn is(target: &HtmlInputElement, other: Option<EventTarget>) -> bool {
+        match other {
+            Some(other) => {
+                let raw: &EventTarget = target.as_ref();
+                *raw == other
+            }
+            None => false,
+        }
+    }
+
+    // Moves the IME element over the canvas and focuses it. The IME element is put in the shadow
+    // root of the canvas, if it is in one, so that both are looked up in the same place.
+    pub fn focus(raw: &HtmlInputElement, canvas: &HtmlCanvasElement, offset: LogicalPosition<f64>) {
+        if !raw.is_connected() {
+            let parent: Option<Node> = match super::shadow_root(canvas) {
+                Some(root) => Some(root.into()),
+                None => web_sys::window()
+                    .and_then(|window| window.document())
+                    .and_then(|document| document.body())
+                    .map(Into::into),
+            };
+            match parent {
+                Some(parent) => {
+                    let _ = parent.append_child(raw);
+                }
+                None => return,
+            }
+        }
+
+        Self::place(raw, canvas, offset);
+        let _ = raw.focus();
+    }
+
+    // The browser shows the IME candidate window next to the IME element, so it is placed at the
+    // given offset from the top left corner of the canvas, e.g. where the text cursor is drawn
+    pub fn place(raw: &HtmlInputElement, canvas: &HtmlCanvasElement, offset: LogicalPosition<f64>) {
+        let bounds = canvas.get_bounding_client_rect();
+        let style = raw.style();
+        let _ = style.set_property("left", &format!("{}px", bounds.x() + offset.x));
+        let _ = style.set_property("top", &format!("{}px", bounds.y() + offset.y));
+    }
+}
@@ -1,15 +1,53 @@
 mod canvas;
+pub mod clipboard;
+mod drag;
 mod event;
+mod event_handle;
+mod frame;
+mod gamepad;
+mod ime;
 mod timeout;
 
 pub use self::canvas::Canvas;
+pub use self::gamepad::Gamepads;
 pub use self::timeout::Timeout;
+pub use web_sys::Worker;
+pub type WorkerMessage = JsValue;
+pub type WorkerListener = EventListenerHandle<dyn FnMut(MessageEvent)>;
 
-use crate::dpi::{LogicalSize, Size};
-use crate::platform::web::WindowExtWebSys;
-use crate::window::Window;
-use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::{window, BeforeUnloadEvent, Element, HtmlCanvasElement, UiEvent};
+use self::event_handle::EventListenerHandle;
+use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
+use crate::error::ExternalError;
+use crate::icon::Icon;
+use crate::platform::web::{
+    Clipboard, CustomCursor, OrientationLock, VisibilityState, WindowExtWebSys,
+};
+use crate::window::{Orientation, Theme, Window};
+use js_sys::{Array, Function, Promise, Reflect};
+use std::cell::{Cell, RefCell};
+use std::path::Path;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, Clamped, JsCast, JsValue};
+use web_sys::{
+    window, BeforeUnloadEvent, CanvasRenderingContext2d, CssStyleDeclaration, Document, Element,
+    File, HtmlCanvasElement, HtmlElement, HtmlInputElement, ImageData, MediaQueryList,
+    MessageEvent, Node, OrientationLockType, OrientationType, ShadowRoot, UiEvent,
+};
+
+pub fn on_worker_message<F>(worker: &Worker, mut handler: F) -> WorkerListener
+where
+    F: 'static + FnMut(WorkerMessage),
+{
+    let closure =
+        Closure::wrap(Box::new(move |event: MessageEvent| handler(event.data()))
+            as Box<dyn FnMut(MessageEvent)>);
+    EventListenerHandle::new(worker, "message", closure)
+}
+
+pub fn queue_microtask(f: impl FnOnce() + 'static) {
+    let window = web_sys::window().expect("Failed to obtain window");
+    window.queue_microtask(Closure::once_into_js(f).unchecked_ref());
+}
 
 pub fn exit_fullscreen() {
     let window = web_sys::window().expect("Failed to obtain window");
@@ -18,6 +56,13 @@ pub fn exit_fullscreen() {
     document.exit_fullscreen();
 }
 
+pub fn exit_pointer_lock() {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let document = window.document().expect("Failed to obtain document");
+
+    document.exit_pointer_lock();
+}
+
 pub fn on_unload(mut handler: impl FnMut() + 'static) {
     let window = web_sys::window().expect("Failed to obtain window");
 
@@ -31,24 +76,131 @@ pub fn on_unload(mut handler: impl FnMut() + 'static) {
 }
 
 pub struct AnimationFrame {
-    closure: Closure<dyn FnMut(f64)>
+    closure: Closure<dyn FnMut(f64)>,
+    handle: Cell<Option<i32>>,
 }
 impl AnimationFrame {
     pub fn new(mut handler: impl FnMut(f64) + 'static) -> Self {
-        let closure = Closure::wrap(
-            Box::new(handler) as _
-        );
-        AnimationFrame { closure }
+        let closure = Closure::wrap(Box::new(handler) as _);
+        AnimationFrame {
+            closure,
+            handle: Cell::new(None),
+        }
     }
     pub fn request_animation_frame(&self) {
-        web_sys::window().unwrap()
+        let handle = web_sys::window()
+            .unwrap()
             .request_animation_frame(self.closure.as_ref().unchecked_ref());
+        self.handle.set(handle.ok());
+    }
+    pub fn cancel_animation_frame(&self) {
+        // Cancelling a frame that has already run has no effect
+        if let Some(handle) = self.handle.take() {
+            let _ = web_sys::window().unwrap().cancel_animation_frame(handle);
+        }
+    }
+}
+
+impl Drop for AnimationFrame {
+    fn drop(&mut self) {
+        self.cancel_animation_frame();
     }
 }
 
 impl WindowExtWebSys for Window {
     fn canvas(&self) -> HtmlCanvasElement {
-        self.window.canvas().raw().clone()
+        self.window.canvas().borrow().raw().clone()
+    }
+
+    fn clipboard(&self) -> Clipboard {
+        Clipboard {
+            inner: self.window.clipboard(),
+        }
+    }
+
+    fn dropped_file(&self, path: &Path) -> Option<File> {
+        self.window.canvas().borrow().dropped_file(path.to_str()?)
+    }
+
+    fn set_scale_factor_override(&self, scale_factor_override: Option<f64>) {
+        self.window.set_scale_factor_override(scale_factor_override)
+    }
+
+    fn theme(&self) -> Theme {
+        theme()
+    }
+
+    fn visibility_state(&self) -> VisibilityState {
+        visibility_state()
+    }
+
+    fn cursor_position(&self) -> Option<PhysicalPosition<f64>> {
+        self.window.cursor_position()
+    }
+
+    fn lock_orientation(&self, lock: OrientationLock) -> Result<(), ExternalError> {
+        self.window.lock_orientation(lock)
+    }
+
+    fn unlock_orientation(&self) {
+        unlock_orientation()
+    }
+
+    fn lock_keyboard(&self, codes: &[&str]) -> Result<(), ExternalError> {
+        self.window.lock_keyboard(codes)
+    }
+
+    fn unlock_keyboard(&self) {
+        unlock_keyboard()
+    }
+
+    fn ready(&self) {
+        self.window.ready()
+    }
+
+    fn request_user_attention(&self) {
+        self.window.request_user_attention()
+    }
+
+    fn set_tab_index(&self, tab_index: Option<i32>) {
+        self.window.set_tab_index(tab_index)
+    }
+
+    fn set_unload_confirmation(&self, confirm: bool) {
+        self.window.set_unload_confirmation(confirm)
+    }
+
+    fn set_auto_size(&self, auto_size: bool) {
+        self.window.set_auto_size(auto_size)
+    }
+
+    fn frame(&self) -> Option<HtmlElement> {
+        let canvas = self.window.canvas().borrow();
+        canvas.frame().map(|frame| frame.raw().clone())
+    }
+
+    fn is_decorated(&self) -> bool {
+        self.window.is_decorated()
+    }
+
+    fn is_maximized(&self) -> bool {
+        self.window.is_maximized()
+    }
+
+    fn set_display_size(&self, size: Size) {
+        self.window.set_display_size(size)
+    }
+
+    fn set_buffer_size(&self, size: PhysicalSize<u32>) {
+        self.window.set_buffer_size(size)
+    }
+
+    fn set_custom_cursor(&self, cursor: Option<CustomCursor>) {
+        self.window.set_custom_cursor(cursor)
+    }
+
+    fn is_minimized(&self) -> bool {
+        self.window.is_minimized()
     }
 }
 
@@ -68,16 +220,190 @@ pub fn window_size() -> LogicalSize<f64> {
     LogicalSize { width, height }
 }
 
+pub fn color_depth() -> u16 {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let screen = window.screen().expect("Failed to obtain screen");
+    let color_depth = screen.color_depth().expect("Failed to get color depth");
+
+    color_depth as u16
+}
+
+pub fn orientation() -> Option<Orientation> {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let screen = window.screen().expect("Failed to obtain screen");
+
+    match screen.orientation().type_() {
+        Ok(OrientationType::PortraitPrimary) => Some(Orientation::PortraitPrimary),
+        Ok(OrientationType::PortraitSecondary) => Some(Orientation::PortraitSecondary),
+        Ok(OrientationType::LandscapePrimary) => Some(Orientation::LandscapePrimary),
+        Ok(OrientationType::LandscapeSecondary) => Some(Orientation::LandscapeSecondary),
+        _ => None,
+    }
+}
+
+// Locking fails right away if the Screen Orientation API is not available, and the handler is
+// called once the browser granted or refused the lock otherwise
+pub fn lock_orientation<F>(lock: OrientationLock, handler: F) -> Result<(), JsValue>
+where
+    F: 'static + FnOnce(Result<(), JsValue>),
+{
+    let window = web_sys::window().expect("Failed to obtain window");
+    let screen = window.screen()?;
+
+    let lock = match lock {
+        OrientationLock::Any => OrientationLockType::Any,
+        OrientationLock::Natural => OrientationLockType::Natural,
+        OrientationLock::Landscape => OrientationLockType::Landscape,
+        OrientationLock::Portrait => OrientationLockType::Portrait,
+        OrientationLock::Exact(Orientation::PortraitPrimary) => {
+            OrientationLockType::PortraitPrimary
+        }
+        OrientationLock::Exact(Orientation::PortraitSecondary) => {
+            OrientationLockType::PortraitSecondary
+        }
+        OrientationLock::Exact(Orientation::LandscapePrimary) => {
+            OrientationLockType::LandscapePrimary
+        }
+        OrientationLock::Exact(Orientation::LandscapeSecondary) => {
+            OrientationLockType::LandscapeSecondary
+        }
+    };
+    let promise = screen.orientation().lock(lock)?;
+    on_settled(&promise, move |result| handler(result.map(|_| ())));
+
+    Ok(())
+}
+
+pub fn unlock_orientation() {
+    let window = web_sys::window().expect("Failed to obtain window");
+    if let Ok(screen) = window.screen() {
+        let _ = screen.orientation().unlock();
+    }
+}
+
+// The Keyboard Lock API is not part of web-sys, so it is looked up on the navigator. An empty
+// list of codes locks all keys.
+pub fn lock_keyboard<F>(codes: &[&str], handler: F) -> Result<(), JsValue>
+where
+    F: 'static + FnOnce(Result<(), JsValue>),
+{
+    let window = web_sys::window().expect("Failed to obtain window");
+    let keyboard = Reflect::get(&window.navigator(), &"keyboard".into())?;
+    let lock: Function = Reflect::get(&keyboard, &"lock".into())?.dyn_into()?;
+
+    let codes: Array = codes.iter().map(|&code| JsValue::from_str(code)).collect();
+    let promise: Promise = lock.call1(&keyboard, &codes)?.dyn_into()?;
+    on_settled(&promise, move |result| handler(result.map(|_| ())));
+
+    Ok(())
+}
+
+pub fn unlock_keyboard() {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let keyboard = match Reflect::get(&window.navigator(), &"keyboard".into()) {
+        Ok(keyboard) => keyboard,
+        Err(_) => return,
+    };
+    if let Ok(unlock) = Reflect::get(&keyboard, &"unlock".into()) {
+        if let Ok(unlock) = unlock.dyn_into::<Function>() {
+            let _ = unlock.call0(&keyboard);
+        }
+    }
+}
+
+// Calls the handler with the outcome of the promise
+pub fn on_settled<F>(promise: &Promise, handler: F)
+where
+    F: 'static + FnOnce(Result<JsValue, JsValue>),
+{
+    let handler = Rc::new(RefCell::new(Some(handler)));
+    let reject_handler = handler.clone();
+
+    let resolve = Closure::once(move |value: JsValue| {
+        if let Some(handler) = handler.borrow_mut().take() {
+            handler(Ok(value));
+        }
+    });
+    let reject = Closure::once(move |error: JsValue| {
+        if let Some(handler) = reject_handler.borrow_mut().take() {
+            handler(Err(error));
+        }
+    });
+
+    let _ = promise.then2(&resolve, &reject);
+
+    // Only one of the closures will ever be called, so they can't free each other. They are
+    // small, and promises are only awaited for rare requests, so they are left to the JS garbage
+    // collector.
+    resolve.forget();
+    reject.forget();
+}
+
+pub fn screen_size() -> LogicalSize<f64> {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let screen = window.screen().expect("Failed to obtain screen");
+    let width = screen.width().expect("Failed to get screen width");
+    let height = screen.height().expect("Failed to get screen height");
+
+    // Some mobile browsers keep reporting the size of the screen in its natural orientation
+    let is_landscape = match screen.orientation().type_() {
+        Ok(OrientationType::LandscapePrimary) | Ok(OrientationType::LandscapeSecondary) => {
+            Some(true)
+        }
+        Ok(OrientationType::PortraitPrimary) | Ok(OrientationType::PortraitSecondary) => {
+            Some(false)
+        }
+        _ => None,
+    };
+    let (width, height) = match is_landscape {
+        Some(is_landscape) if is_landscape != (width > height) => (height, width),
+        _ => (width, height),
+    };
+
+    LogicalSize {
+        width: width.into(),
+        height: height.into(),
+    }
+}
+
 pub fn scale_factor() -> f64 {
     let window = web_sys::window().expect("Failed to obtain window");
     window.device_pixel_ratio()
 }
 
-pub fn set_canvas_size(raw: &HtmlCanvasElement, size: Size) {
-    let scale_factor = scale_factor();
+pub fn computed_style(element: &Element) -> Option<CssStyleDeclaration> {
+    web_sys::window()?.get_computed_style(element).ok()?
+}
 
+// Computed lengths are always in pixels, e.g. `12.5px`
+pub fn css_pixels(style: &CssStyleDeclaration, property: &str) -> f64 {
+    style
+        .get_property_value(property)
+        .ok()
+        .and_then(|value| value.trim_end_matches("px").parse().ok())
+        .unwrap_or(0.0)
+}
+
+// The canvas of every window holds the id of the window in its `data-raw-handle` attribute
+pub fn raw_handle(canvas: &HtmlCanvasElement) -> Option<u32> {
+    canvas.get_attribute("data-raw-handle")?.parse().ok()
+}
+
+// The size of the drawing buffer
+pub fn canvas_size(raw: &HtmlCanvasElement) -> PhysicalSize<u32> {
+    PhysicalSize {
+        width: raw.width(),
+        height: raw.height(),
+    }
+}
+
+// The CSS size is derived from the drawing buffer, rounded to whole device pixels, rather than from
+// the requested size. With a fractional scale factor, a logical size would otherwise be displayed
+// across a fraction of a device pixel more or less than the drawing buffer, which browsers
+// stretch, blurring the canvas.
+pub fn set_canvas_size(raw: &HtmlCanvasElement, size: Size, scale_factor: f64) {
     let physical_size = size.to_physical::<u32>(scale_factor);
-    let logical_size = size.to_logical::<f64>(scale_factor);
+    let logical_size = physical_size.to_logical::<f64>(scale_factor);
 
     raw.set_width(physical_size.width);
     raw.set_height(physical_size.height);
@@ -91,11 +417,192 @@ pub fn set_canvas_size(raw: &HtmlCanvasElement, size: Size) {
         .expect("Failed to set canvas height");
 }
 
+// The shadow root the element is in, if any. The document only knows the host of a shadow root,
+// so the focused, fullscreen and pointer lock elements within it are looked up in the shadow root.
+pub fn shadow_root(element: &Element) -> Option<ShadowRoot> {
+    element.get_root_node().dyn_into().ok()
+}
+
+// The canvas counts as focused while its IME element is
+pub fn is_focused(canvas: &HtmlCanvasElement, ime: &HtmlInputElement) -> bool {
+    let active_element = match shadow_root(canvas) {
+        Some(root) => root.active_element(),
+        None => window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element()),
+    };
+
+    match active_element {
+        Some(element) => {
+            let canvas: &Element = canvas.as_ref();
+            let ime: &Element = ime.as_ref();
+            element == *canvas || element == *ime
+        }
+        None => false,
+    }
+}
+
+// The media query matching the dark color scheme, if the browser supports it
+pub fn dark_mode_query() -> Option<MediaQueryList> {
+    window()?
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+}
+
+// The media query matching the current `devicePixelRatio`, which stops matching once it changes
+pub fn resolution_query() -> Option<MediaQueryList> {
+    let window = window()?;
+    let query = format!("(resolution: {}dppx)", window.device_pixel_ratio());
+    window.match_media(&query).ok().flatten()
+}
+
+pub fn theme() -> Theme {
+    match dark_mode_query() {
+        Some(query) if query.matches() => Theme::Dark,
+        _ => Theme::Light,
+    }
+}
+
+pub fn set_title(title: &str) {
+    if let Some(document) = window().and_then(|window| window.document()) {
+        document.set_title(title);
+    }
+}
+
+// The favicon is replaced by adding a link after the ones of the page, which takes precedence.
+// Removing it shows the favicon of the page again.
+pub fn set_favicon(icon: Option<&Icon>) {
+    let document = match window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return,
+    };
+
+    if let Ok(Some(link)) = document.query_selector("link[data-winit-icon]") {
+        link.remove();
+    }
+
+    let (icon, head) = match (icon, document.head()) {
+        (Some(icon), Some(head)) => (icon, head),
+        _ => return,
+    };
+
+    let url = match icon_url(&document, icon) {
+        Ok(url) => url,
+        Err(error) => {
+            warn!("Failed to encode the window icon: {:?}", error);
+            return;
+        }
+    };
+
+    let link = document
+        .create_element("link")
+        .expect("Failed to create link");
+    let _ = link.set_attribute("data-winit-icon", "");
+    let _ = link.set_attribute("rel", "icon");
+    let _ = link.set_attribute("sizes", &format!("{}x{}", icon.width, icon.height));
+    let _ = link.set_attribute("href", &url);
+    let _ = head.append_child(&link);
+}
+
+// Browsers ignore cursor images that are larger than this in either dimension
+const MAX_CURSOR_SIZE: u32 = 128;
+
+// The value of the `cursor` property showing the image, which falls back to the default cursor if
+// the browser can't show it
+pub fn custom_cursor(cursor: &CustomCursor) -> Result<String, JsValue> {
+    let image = &cursor.image;
+    if image.width > MAX_CURSOR_SIZE || image.height > MAX_CURSOR_SIZE {
+        return Err(JsValue::from_str("The cursor image is too large"));
+    }
+
+    let document = window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("Failed to obtain document"))?;
+    let url = icon_url(&document, image)?;
+    // The hotspot has to be within the image
+    let x = cursor.hotspot_x.min(image.width.saturating_sub(1));
+    let y = cursor.hotspot_y.min(image.height.saturating_sub(1));
+
+    Ok(format!("url({}) {} {}, auto", url, x, y))
+}
+
+// Encodes the icon as a PNG data URL by drawing it on a detached canvas
+fn icon_url(document: &Document, icon: &Icon) -> Result<String, JsValue> {
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.unchecked_into();
+    canvas.set_width(icon.width);
+    canvas.set_height(icon.height);
+
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2D context unavailable"))?
+        .unchecked_into();
+    let image =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&icon.rgba), icon.width, icon.height)?;
+    context.put_image_data(&image, 0.0, 0.0)?;
+
+    canvas.to_data_url()
+}
+
+pub fn document_has_focus() -> bool {
+    window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.has_focus().ok())
+        .unwrap_or(false)
+}
+
+pub fn is_hidden() -> bool {
+    window()
+        .and_then(|window| window.document())
+        .map(|document| document.hidden())
+        .unwrap_or(false)
+}
+
+// `visibilityState` is read as a string, as `web_sys::VisibilityState` doesn't know about the
+// `prerender` state some browsers still report
+pub fn visibility_state() -> VisibilityState {
+    let state = window()
+        .and_then(|window| window.document())
+        .and_then(|document| Reflect::get(&document, &"visibilityState".into()).ok())
+        .and_then(|state| state.as_string());
+
+    match state.as_deref() {
+        Some("hidden") => VisibilityState::Hidden,
+        Some("prerender") => VisibilityState::Prerender,
+        _ => VisibilityState::Visible,
+    }
+}
+
 pub fn is_fullscreen(canvas: &HtmlCanvasElement) -> bool {
-    let window = window().expect("Failed to obtain window");
-    let document = window.document().expect("Failed to obtain document");
+    let fullscreen_element = match shadow_root(canvas) {
+        Some(root) => root.fullscreen_element(),
+        None => {
+            let window = window().expect("Failed to obtain window");
+            let document = window.document().expect("Failed to obtain document");
+            document.fullscreen_element()
+        }
+    };
+
+    match fullscreen_element {
+        Some(elem) => {
+            let raw: Element = canvas.clone().into();
+            raw == elem
+        }
+        None => false,
+    }
+}
+
+pub fn is_pointer_locked(canvas: &HtmlCanvasElement) -> bool {
+    let pointer_lock_element = match shadow_root(canvas) {
+        Some(root) => root.pointer_lock_element(),
+        None => {
+            let window = window().expect("Failed to obtain window");
+            let document = window.document().expect("Failed to obtain document");
+            document.pointer_lock_element()
+        }
+    };
 
-    match document.fullscreen_element() {
+    match pointer_lock_element {
         Some(elem) => {
             let raw: Element = canvas.clone().into();
             raw == elem
@@ -105,3 +612,4 @@ pub fn is_fullscreen(canvas: &HtmlCanvasElement) -> bool {
 }
 
 pub type RawCanvasType = HtmlCanvasElement;
+pub type RawContainerType = Node;
@@ -1,116 +1,346 @@
 use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
+use crate::event::{Event, ModifiersState, VirtualKeyCode, WindowEvent};
 use crate::icon::Icon;
 use crate::monitor::MonitorHandle as RootMH;
-use crate::window::{CursorIcon, Fullscreen, WindowAttributes, WindowId as RootWI};
+#[cfg(feature = "web-sys")]
+use crate::platform::web::CustomCursor;
+use crate::platform::web::{CursorMoveCoalescing, OrientationLock};
+use crate::window::{CursorGrabMode, CursorIcon, Fullscreen, WindowAttributes, WindowId as RootWI};
 
-use raw_window_handle::web::WebHandle;
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle, WebDisplayHandle, WebWindowHandle};
 
-use super::{backend, monitor, EventLoopWindowTarget};
+use super::{backend, monitor, Clipboard, EventLoopWindowTarget};
 
-use std::cell::RefCell;
-use std::collections::vec_deque::IntoIter as VecDequeIter;
-use std::collections::VecDeque;
+use std::cell::{Cell, RefCell};
+use std::iter;
+use std::rc::Rc;
 
 pub struct Window {
-    canvas: backend::Canvas,
+    canvas: Rc<RefCell<backend::Canvas>>,
     previous_pointer: RefCell<&'static str>,
+    custom_cursor: RefCell<Option<String>>,
+    cursor_visible: Cell<bool>,
+    visible: Cell<bool>,
+    minimized: Cell<bool>,
     id: Id,
     register_redraw_request: Box<dyn Fn()>,
+    resize: Box<dyn Fn(PhysicalSize<u32>)>,
+    override_inner_size: Box<dyn Fn(PhysicalSize<u32>) -> bool>,
+    set_title: Box<dyn Fn(&str)>,
+    request_attention: Box<dyn Fn()>,
+    title: RefCell<String>,
+    cursor_position: Box<dyn Fn() -> Option<PhysicalPosition<f64>>>,
+    send_event: Rc<dyn Fn(WindowEvent)>,
 }
 
 impl Window {
     pub fn new<T>(
         target: &EventLoopWindowTarget<T>,
         attr: WindowAttributes,
-        platform_attr: PlatformSpecificBuilderAttributes,
+        mut platform_attr: PlatformSpecificBuilderAttributes,
     ) -> Result<Self, RootOE> {
+        target.apply_options(&mut platform_attr);
         let runner = target.runner.clone();
 
         let id = target.generate_id();
 
-        let mut canvas = backend::Canvas::create(platform_attr)?;
+        // A canvas provided by the user keeps its size unless one is requested explicitly, as does
+        // any canvas that isn't sized automatically
+        let inner_size = match (attr.inner_size, &platform_attr.canvas) {
+            (Some(size), _) => Some(size),
+            (None, Some(_)) => None,
+            (None, None) if platform_attr.manual_size => None,
+            (None, None) => Some(Size::Logical(LogicalSize {
+                width: 1024.0,
+                height: 768.0,
+            })),
+        };
+
+        let focus = platform_attr.focus;
+        if platform_attr.hidden_until_ready {
+            runner.reveal_after_redraw(RootWI(id));
+        }
+        let canvas = Rc::new(RefCell::new(backend::Canvas::create(platform_attr)?));
 
         let register_redraw_request = Box::new(move || runner.request_redraw(RootWI(id)));
 
-        target.register(&mut canvas, id);
+        let runner = target.runner.clone();
+        let resize = Box::new(move |size| runner.coalesce_resize(RootWI(id), size));
+
+        let runner = target.runner.clone();
+        let override_inner_size =
+            Box::new(move |size| runner.override_inner_size(RootWI(id), size));
+
+        let runner = target.runner.clone();
+        let set_title = Box::new(move |title: &str| runner.set_title(RootWI(id), title));
+
+        let runner = target.runner.clone();
+        let request_attention = Box::new(move || runner.request_attention(RootWI(id)));
+
+        let runner = target.runner.clone();
+        let cursor_position = Box::new(move || runner.cursor_position(RootWI(id)));
+
+        let runner = target.runner.clone();
+        let send_event = Rc::new(move |event| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWI(id),
+                event,
+            })
+        });
+
+        target.register(&canvas, id);
 
         let window = Window {
             canvas,
             previous_pointer: RefCell::new("auto"),
+            custom_cursor: RefCell::new(None),
+            cursor_visible: Cell::new(true),
+            visible: Cell::new(true),
+            minimized: Cell::new(false),
             id,
             register_redraw_request,
+            resize,
+            override_inner_size,
+            set_title,
+            request_attention,
+            title: RefCell::new(String::new()),
+            cursor_position,
+            send_event,
         };
 
-        window.set_inner_size(attr.inner_size.unwrap_or(Size::Logical(LogicalSize {
-            width: 1024.0,
-            height: 768.0,
-        })));
+        window.set_min_inner_size(attr.min_inner_size);
+        window.set_max_inner_size(attr.max_inner_size);
+        if let Some(size) = inner_size {
+            window.set_inner_size(size);
+        }
         window.set_title(&attr.title);
+        window.set_decorations(attr.decorations);
+        // The frame drawing the decorations is positioned in place of the canvas
+        if let Some(position) = attr.position {
+            window.set_outer_position(position);
+        }
         window.set_maximized(attr.maximized);
         window.set_visible(attr.visible);
-        window.set_window_icon(attr.window_icon);
+        // Windows created without an icon keep the favicon of the page, or of another window
+        if attr.window_icon.is_some() {
+            window.set_window_icon(attr.window_icon);
+        }
+        if focus {
+            window.canvas.borrow().focus_later();
+        }
 
         Ok(window)
     }
 
-    pub fn canvas(&self) -> &backend::Canvas {
+    pub fn canvas(&self) -> &Rc<RefCell<backend::Canvas>> {
         &self.canvas
     }
 
+    pub fn clipboard(&self) -> Clipboard {
+        Clipboard::new(self.send_event.clone(), self.canvas.borrow().user_gesture())
+    }
+
+    pub fn cursor_position(&self) -> Option<PhysicalPosition<f64>> {
+        (self.cursor_position)()
+    }
+
+    pub fn set_unload_confirmation(&self, confirm: bool) {
+        self.canvas.borrow().set_unload_confirmation(confirm);
+    }
+
+    pub fn set_tab_index(&self, tab_index: Option<i32>) {
+        self.canvas.borrow().set_tab_index(tab_index);
+    }
+
+    pub fn lock_orientation(&self, lock: OrientationLock) -> Result<(), ExternalError> {
+        if !self.canvas.borrow().is_fullscreen() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        backend::lock_orientation(lock, |result| {
+            if let Err(error) = result {
+                warn!("Failed to lock the screen orientation: {:?}", error);
+            }
+        })
+        .map_err(|_| ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn ready(&self) {
+        self.canvas.borrow().reveal();
+    }
+
+    // Nothing needs the attention of the user while the canvas is focused in the focused page
+    pub fn request_user_attention(&self) {
+        if !self.canvas.borrow().has_focus() {
+            (self.request_attention)();
+        }
+    }
+
+    pub fn lock_keyboard(&self, codes: &[&str]) -> Result<(), ExternalError> {
+        if !self.canvas.borrow().is_fullscreen() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        backend::lock_keyboard(codes, |result| {
+            if let Err(error) = result {
+                warn!("Failed to lock the keyboard: {:?}", error);
+            }
+        })
+        .map_err(|_| ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_title(&self, title: &str) {
-        self.canvas.set_attribute("alt", title);
+        let canvas = self.canvas.borrow();
+        canvas.set_attribute("alt", title);
+        if let Some(frame) = canvas.frame() {
+            frame.set_title(title);
+        }
+        *self.title.borrow_mut() = title.to_owned();
+        (self.set_title)(title);
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+        self.update_visibility();
+    }
+
+    pub fn is_visible(&self) -> Option<bool> {
+        let visible = self.visible.get() && !self.minimized.get();
+        Some(visible && !backend::is_hidden() && self.canvas.borrow().is_displayed())
     }
 
-    pub fn set_visible(&self, _visible: bool) {
-        // Intentionally a no-op
+    // A minimized window hides its canvas like an invisible one, and the window counts as occluded
+    // while either its canvas or the whole page is hidden
+    fn update_visibility(&self) {
+        let visible = self.visible.get() && !self.minimized.get();
+        if self.canvas.borrow().set_visible(visible) {
+            (self.send_event)(WindowEvent::Occluded(!visible || backend::is_hidden()));
+        }
     }
 
     pub fn request_redraw(&self) {
         (self.register_redraw_request)();
     }
 
+    // Includes the frame drawing the decorations, if the window is decorated
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
-        Ok(self.canvas.position().to_physical(self.scale_factor()))
+        Ok(self
+            .canvas
+            .borrow()
+            .outer_position()
+            .to_physical(self.scale_factor()))
     }
 
+    // The border and padding of the canvas take the place of window decorations
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
-        // Note: the canvas element has no window decorations, so this is equal to `outer_position`.
-        self.outer_position()
+        Ok(self
+            .canvas
+            .borrow()
+            .content_position()
+            .to_physical(self.scale_factor()))
     }
 
     pub fn set_outer_position(&self, position: Position) {
         let position = position.to_logical::<f64>(self.scale_factor());
 
-        self.canvas.set_attribute("position", "fixed");
-        self.canvas.set_attribute("left", &position.x.to_string());
-        self.canvas.set_attribute("top", &position.y.to_string());
+        let canvas = self.canvas.borrow();
+        canvas.set_outer_style("position", "fixed");
+        canvas.set_outer_style("left", &format!("{}px", position.x));
+        canvas.set_outer_style("top", &format!("{}px", position.y));
     }
 
     #[inline]
     pub fn inner_size(&self) -> PhysicalSize<u32> {
-        self.canvas.size()
+        self.canvas.borrow().size()
     }
 
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
-        // Note: the canvas element has no window decorations, so this is equal to `inner_size`.
-        self.inner_size()
+        // Note: without a frame, the canvas has no window decorations, so this is equal to
+        // `inner_size`.
+        match self.canvas.borrow().frame_size() {
+            Some(size) => size.to_physical(self.scale_factor()),
+            None => self.inner_size(),
+        }
     }
 
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
-        backend::set_canvas_size(self.canvas.raw(), size);
+        let scale_factor = self.scale_factor();
+        let new_size = self
+            .canvas
+            .borrow()
+            .size_constraints()
+            .clamp(size.to_physical(scale_factor), scale_factor);
+        if (self.override_inner_size)(new_size) {
+            return;
+        }
+        let old_size = self.inner_size();
+
+        backend::set_canvas_size(
+            self.canvas.borrow().raw(),
+            Size::Physical(new_size),
+            scale_factor,
+        );
+
+        // The resize observer only reports changes of the displayed size that the drawing buffer
+        // doesn't already match, so the resize is reported here
+        if new_size != old_size {
+            (self.resize)(new_size);
+            (self.register_redraw_request)();
+        }
+    }
+
+    // Unlike `set_inner_size`, only the CSS size of the canvas is changed, and the drawing buffer is
+    // left to automatic sizing
+    pub fn set_display_size(&self, size: Size) {
+        let size = size.to_logical::<f64>(self.scale_factor());
+
+        let canvas = self.canvas.borrow();
+        canvas.set_style("width", &format!("{}px", size.width));
+        canvas.set_style("height", &format!("{}px", size.height));
+    }
+
+    // Only the drawing buffer is resized, which the canvas is stretched to its CSS size for
+    pub fn set_buffer_size(&self, size: PhysicalSize<u32>) {
+        let old_size = self.inner_size();
+        {
+            let canvas = self.canvas.borrow();
+            canvas.raw().set_width(size.width);
+            canvas.raw().set_height(size.height);
+        }
+
+        if size != old_size {
+            (self.resize)(size);
+            (self.register_redraw_request)();
+        }
     }
 
     #[inline]
-    pub fn set_min_inner_size(&self, _dimensions: Option<Size>) {
-        // Intentionally a no-op: users can't resize canvas elements
+    pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
+        self.canvas.borrow().set_min_size(dimensions);
+        self.apply_size_constraints();
     }
 
     #[inline]
-    pub fn set_max_inner_size(&self, _dimensions: Option<Size>) {
-        // Intentionally a no-op: users can't resize canvas elements
+    pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
+        self.canvas.borrow().set_max_size(dimensions);
+        self.apply_size_constraints();
+    }
+
+    // Resize the canvas only if its current size violates the constraints, so that a canvas sized
+    // by the page keeps its CSS size otherwise
+    fn apply_size_constraints(&self) {
+        let size = self.inner_size();
+        let clamped_size = self
+            .canvas
+            .borrow()
+            .size_constraints()
+            .clamp(size, self.scale_factor());
+        if clamped_size != size {
+            self.set_inner_size(Size::Physical(clamped_size));
+        }
     }
 
     #[inline]
@@ -120,7 +350,14 @@ impl Window {
 
     #[inline]
     pub fn scale_factor(&self) -> f64 {
-        super::backend::scale_factor()
+        self.canvas.borrow().scale_factor()
+    }
+
+    #[inline]
+    pub fn set_scale_factor_override(&self, scale_factor_override: Option<f64>) {
+        self.canvas
+            .borrow()
+            .set_scale_factor_override(scale_factor_override);
     }
 
     #[inline]
@@ -165,63 +402,135 @@ impl Window {
             CursorIcon::RowResize => "row-resize",
         };
         *self.previous_pointer.borrow_mut() = text;
-        self.canvas
-            .set_attribute("style", &format!("cursor: {}", text));
+        *self.custom_cursor.borrow_mut() = None;
+        self.apply_cursor();
+    }
+
+    // Images that can't be shown are replaced by the default cursor
+    #[cfg(feature = "web-sys")]
+    pub fn set_custom_cursor(&self, cursor: Option<CustomCursor>) {
+        let style = cursor.map(|cursor| {
+            backend::custom_cursor(&cursor).unwrap_or_else(|error| {
+                warn!("Failed to set the custom cursor: {:?}", error);
+                "auto".to_owned()
+            })
+        });
+        *self.custom_cursor.borrow_mut() = style;
+        self.apply_cursor();
+    }
+
+    // A custom cursor takes the place of the cursor icon, and a hidden cursor stays hidden until it
+    // is made visible again
+    fn apply_cursor(&self) {
+        if !self.cursor_visible.get() {
+            return;
+        }
+        let custom_cursor = self.custom_cursor.borrow();
+        let cursor = match *custom_cursor {
+            Some(ref cursor) => cursor.as_str(),
+            None => *self.previous_pointer.borrow(),
+        };
+        self.canvas.borrow().set_style("cursor", cursor);
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
-        // Intentionally a no-op, as the web does not support setting cursor positions
+    pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
+        // The system cursor can't be moved, but while the pointer is locked the position reported
+        // by `CursorMoved` is virtual, and continues from the given one
+        let canvas = self.canvas.borrow();
+        if !canvas.is_pointer_locked() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+        canvas
+            .locked_cursor_position()
+            .set(position.to_physical(self.scale_factor()));
         Ok(())
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, _grab: bool) -> Result<(), ExternalError> {
-        // Intentionally a no-op, as the web does not (properly) support grabbing the cursor
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        match mode {
+            CursorGrabMode::Locked => self.canvas.borrow().request_pointer_lock(),
+            CursorGrabMode::None => {
+                if self.canvas.borrow().is_pointer_locked() {
+                    backend::exit_pointer_lock();
+                }
+            }
+            // Browsers offer no way to keep the cursor within an element
+            CursorGrabMode::Confined => {
+                return Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+        }
         Ok(())
     }
 
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
+        self.cursor_visible.set(visible);
         if !visible {
-            self.canvas.set_attribute("cursor", "none");
+            self.canvas.borrow().set_style("cursor", "none");
         } else {
-            self.canvas
-                .set_attribute("cursor", *self.previous_pointer.borrow());
+            self.apply_cursor();
         }
     }
 
     #[inline]
-    pub fn set_minimized(&self, _minimized: bool) {
-        // Intentionally a no-op, as canvases cannot be 'minimized'
+    pub fn set_minimized(&self, minimized: bool) {
+        self.minimized.set(minimized);
+        self.update_visibility();
     }
 
+    pub fn is_minimized(&self) -> bool {
+        self.minimized.get()
+    }
+
+    // The new size is reported by the resize observer once the canvas has been laid out again
     #[inline]
-    pub fn set_maximized(&self, _maximized: bool) {
-        // Intentionally a no-op, as canvases cannot be 'maximized'
+    pub fn set_maximized(&self, maximized: bool) {
+        self.canvas.borrow().set_maximized(maximized);
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.canvas.borrow().is_maximized()
     }
 
     #[inline]
     pub fn fullscreen(&self) -> Option<Fullscreen> {
-        if self.canvas.is_fullscreen() {
+        if self.canvas.borrow().is_fullscreen() {
             Some(Fullscreen::Borderless(self.current_monitor()))
         } else {
             None
         }
     }
 
+    // Exclusive fullscreen enters borderless fullscreen as well, as its only video mode is the
+    // current one
     #[inline]
     pub fn set_fullscreen(&self, monitor: Option<Fullscreen>) {
         if monitor.is_some() {
-            self.canvas.request_fullscreen();
-        } else if self.canvas.is_fullscreen() {
+            self.canvas.borrow().request_fullscreen();
+        } else if self.canvas.borrow().is_fullscreen() {
             backend::exit_fullscreen();
         }
     }
 
     #[inline]
-    pub fn set_decorations(&self, _decorations: bool) {
-        // Intentionally a no-op, no canvas decorations
+    pub fn set_decorations(&self, decorations: bool) {
+        let result = self
+            .canvas
+            .borrow_mut()
+            .set_decorations(decorations, &self.title.borrow());
+        if let Err(error) = result {
+            warn!("Failed to set the decorations of the canvas: {}", error);
+        }
+    }
+
+    pub fn set_auto_size(&self, auto_size: bool) {
+        self.canvas.borrow().set_auto_size(auto_size);
+    }
+
+    pub fn is_decorated(&self) -> bool {
+        self.canvas.borrow().frame().is_some()
     }
 
     #[inline]
@@ -230,13 +539,32 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_window_icon(&self, _window_icon: Option<Icon>) {
-        // Currently an intentional no-op
+    pub fn set_window_icon(&self, window_icon: Option<Icon>) {
+        backend::set_favicon(window_icon.as_ref());
+    }
+
+    #[inline]
+    pub fn set_ime_position(&self, position: Position) {
+        let position = position.to_logical(self.scale_factor());
+        self.canvas.borrow().set_ime_position(position);
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.canvas.borrow().set_ime_allowed(allowed);
     }
 
     #[inline]
-    pub fn set_ime_position(&self, _position: Position) {
-        // Currently a no-op as it does not seem there is good support for this on web
+    pub fn focus_window(&self) {
+        self.canvas.borrow().focus();
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        self.canvas
+            .borrow()
+            .drag_window()
+            .map_err(ExternalError::Os)
     }
 
     #[inline]
@@ -247,8 +575,8 @@ impl Window {
     }
 
     #[inline]
-    pub fn available_monitors(&self) -> VecDequeIter<monitor::Handle> {
-        VecDeque::new().into_iter()
+    pub fn available_monitors(&self) -> iter::Once<monitor::Handle> {
+        iter::once(monitor::Handle)
     }
 
     #[inline]
@@ -262,13 +590,15 @@ impl Window {
     }
 
     #[inline]
-    pub fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
-        let handle = WebHandle {
-            id: self.id.0,
-            ..WebHandle::empty()
-        };
+    pub fn raw_window_handle(&self) -> RawWindowHandle {
+        let mut handle = WebWindowHandle::empty();
+        handle.id = self.id.0;
 
-        raw_window_handle::RawWindowHandle::Web(handle)
+        RawWindowHandle::Web(handle)
+    }
+
+    pub fn raw_display_handle(&self) -> RawDisplayHandle {
+        RawDisplayHandle::Web(WebDisplayHandle::empty())
     }
 }
 
@@ -279,9 +609,31 @@ impl Id {
     pub unsafe fn dummy() -> Id {
         Id(0)
     }
+
+    #[cfg(feature = "web-sys")]
+    pub fn from_canvas(canvas: &backend::RawCanvasType) -> Option<Id> {
+        backend::raw_handle(canvas).map(Id)
+    }
+
+    pub fn raw_handle(&self) -> u32 {
+        self.0
+    }
 }
 
 #[derive(Default, Clone)]
 pub struct PlatformSpecificBuilderAttributes {
     pub(crate) canvas: Option<backend::RawCanvasType>,
+    pub(crate) container: Option<backend::RawContainerType>,
+    pub(crate) ignore_key_repeat: bool,
+    pub(crate) prevented_keys: Vec<(VirtualKeyCode, ModifiersState)>,
+    pub(crate) cursor_move_coalescing: CursorMoveCoalescing,
+    pub(crate) passive_listeners: Option<bool>,
+    pub(crate) prevent_default: Option<bool>,
+    pub(crate) prevent_context_menu: bool,
+    pub(crate) tab_index: Option<i32>,
+    pub(crate) focus: bool,
+    pub(crate) manual_size: bool,
+    pub(crate) pointer_capture: bool,
+    pub(crate) hidden_until_ready: bool,
+    pub(crate) prevent_touch_action: bool,
 }
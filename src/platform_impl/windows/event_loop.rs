@@ -1175,6 +1175,7 @@ unsafe extern "system" fn public_window_callback<T: 'static>(
                             },
                             location,
                             force: None, // WM_TOUCH doesn't support pressure information
+                            radius: None,
                             id: input.dwID as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -1314,6 +1315,7 @@ unsafe extern "system" fn public_window_callback<T: 'static>(
                             },
                             location,
                             force,
+                            radius: None,
                             id: pointer_info.pointerId as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -116,6 +116,17 @@ pub struct WindowAttributes {
     /// The default is `None`.
     pub max_inner_size: Option<Size>,
 
+    /// The desired position of the window. If this is `None`, some platform-specific position
+    /// will be chosen.
+    ///
+    /// The default is `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Applied like `Window::set_outer_position`.
+    /// - Ignored on other platforms.
+    pub position: Option<Position>,
+
     /// Whether the window is resizable or not.
     ///
     /// The default is `true`.
@@ -170,6 +181,7 @@ impl Default for WindowAttributes {
             inner_size: None,
             min_inner_size: None,
             max_inner_size: None,
+            position: None,
             resizable: true,
             title: "winit window".to_owned(),
             maximized: false,
@@ -223,6 +235,15 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets the desired position of the window.
+    ///
+    /// See [`WindowAttributes::position`] for details.
+    #[inline]
+    pub fn with_position<P: Into<Position>>(mut self, position: P) -> Self {
+        self.window.position = Some(position.into());
+        self
+    }
+
     /// Sets whether the window is resizable or not.
     ///
     /// See [`Window::set_resizable`] for details.
@@ -379,6 +400,8 @@ impl Window {
     /// - **Android:** Always returns 1.0.
     /// - **iOS:** Can only be called on the main thread. Returns the underlying `UIView`'s
     ///   [`contentScaleFactor`].
+    /// - **Web:** Returns the device pixel ratio, unless overridden with
+    ///   `WindowExtWebSys::set_scale_factor_override`.
     ///
     /// [`contentScaleFactor`]: https://developer.apple.com/documentation/uikit/uiview/1622657-contentscalefactor?language=objc
     #[inline]
@@ -418,8 +441,8 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the top left coordinates of the
     ///   window's [safe area] in the screen space coordinate system.
-    /// - **Web:** Returns the top-left coordinates relative to the viewport. _Note: this returns the
-    ///    same value as `outer_position`._
+    /// - **Web:** Returns the top-left coordinates of the content box of the canvas relative to the
+    ///   viewport, which excludes its border and padding. It changes as the page scrolls.
     ///
     /// [safe area]: https://developer.apple.com/documentation/uikit/uiview/2891103-safeareainsets?language=objc
     #[inline]
@@ -441,7 +464,9 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the top left coordinates of the
     ///   window in the screen space coordinate system.
-    /// - **Web:** Returns the top-left coordinates relative to the viewport.
+    /// - **Web:** Returns the top-left coordinates of the canvas, including its border, relative to
+    ///   the viewport, or of the frame drawing its decorations if it is decorated. It changes as
+    ///   the page scrolls.
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         self.window.outer_position()
@@ -456,7 +481,8 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread. Sets the top left coordinates of the
     ///   window in the screen space coordinate system.
-    /// - **Web:** Sets the top-left coordinates relative to the viewport.
+    /// - **Web:** Sets the top-left coordinates relative to the viewport, by giving the canvas, or
+    ///   the frame drawing its decorations, a fixed position.
     #[inline]
     pub fn set_outer_position<P: Into<Position>>(&self, position: P) {
         self.window.set_outer_position(position.into())
@@ -487,7 +513,10 @@ impl Window {
     ///
     /// - **iOS:** Unimplemented. Currently this panics, as it's not clear what `set_inner_size`
     ///   would mean for iOS.
-    /// - **Web:** Sets the size of the canvas element.
+    /// - **Web:** Sets the size of the canvas element, both its drawing buffer and its displayed CSS
+    ///   size, and emits `WindowEvent::Resized` if the size changed. The size is rounded to whole
+    ///   physical pixels, and the CSS size derived from it, so that the canvas isn't stretched by a
+    ///   fraction of a pixel with a fractional scale factor.
     #[inline]
     pub fn set_inner_size<S: Into<Size>>(&self, size: S) {
         self.window.set_inner_size(size.into())
@@ -502,8 +531,8 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the `PhysicalSize` of the window in
     ///   screen space coordinates.
-    /// - **Web:** Returns the size of the canvas element. _Note: this returns the same value as
-    ///   `inner_size`._
+    /// - **Web:** Returns the size of the frame drawing the decorations if the window is
+    ///   decorated. _Note: otherwise this returns the same value as `inner_size`._
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
         self.window.outer_size()
@@ -514,7 +543,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Has no effect.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Also sets the corresponding CSS property of the canvas element, so that the layout
+    ///   of the page respects it.
     #[inline]
     pub fn set_min_inner_size<S: Into<Size>>(&self, min_size: Option<S>) {
         self.window.set_min_inner_size(min_size.map(|s| s.into()))
@@ -525,7 +555,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Has no effect.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Also sets the corresponding CSS property of the canvas element, so that the layout
+    ///   of the page respects it.
     #[inline]
     pub fn set_max_inner_size<S: Into<Size>>(&self, max_size: Option<S>) {
         self.window.set_max_inner_size(max_size.map(|s| s.into()))
@@ -539,6 +570,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - Has no effect on iOS.
+    /// - **Web:** Sets the title of the page. With several windows, the title of the most
+    ///   recently focused one is shown.
     #[inline]
     pub fn set_title(&self, title: &str) {
         self.window.set_title(title)
@@ -551,12 +584,26 @@ impl Window {
     ///
     /// - **Android:** Has no effect.
     /// - **iOS:** Can only be called on the main thread.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Hides the canvas with `display: none`, which takes the focus from it, and emits
+    ///   `WindowEvent::Occluded`.
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         self.window.set_visible(visible)
     }
 
+    /// Returns whether the window is visible, or `None` if this can't be determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Windows / X11 / Wayland:** Unsupported, always `None`.
+    /// - **Web:** Whether the canvas is shown, i.e. neither hidden by `set_visible(false)`,
+    ///   `set_minimized(true)` or its `display` and `visibility` styles, nor in a page that is in
+    ///   the background.
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        self.window.is_visible()
+    }
+
     /// Sets whether the window is resizable or not.
     ///
     /// Note that making the window unresizable doesn't exempt you from handling `Resized`, as that event can still be
@@ -582,6 +629,7 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Has no effect
+    /// - **Web:** Hides the canvas, like `set_visible(false)`, until the window is restored.
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
         self.window.set_minimized(minimized);
@@ -592,7 +640,7 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Has no effect.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Sizes the canvas to fill the viewport.
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         self.window.set_maximized(maximized)
@@ -617,6 +665,9 @@ impl Window {
     /// - **iOS:** Can only be called on the main thread.
     /// - **Wayland:** Does not support exclusive fullscreen mode.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
+    /// - **Web:** Entering fullscreen only works while handling an input event, such as
+    ///   `WindowEvent::MouseInput`, as browsers require a user gesture. Otherwise a warning is
+    ///   logged. `Fullscreen::Exclusive` behaves like `Fullscreen::Borderless`.
     #[inline]
     pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         self.window.set_fullscreen(fullscreen)
@@ -637,7 +688,8 @@ impl Window {
     /// ## Platform-specific
     /// - **iOS:** Can only be called on the main thread. Controls whether the status bar is hidden
     ///   via [`setPrefersStatusBarHidden`].
-    /// - **Web:** Has no effect.
+    /// - **Web:** Decorations are drawn by an element with a title bar that takes the place of the
+    ///   canvas in the document, see `WindowExtWebSys::frame`.
     ///
     /// [`setPrefersStatusBarHidden`]: https://developer.apple.com/documentation/uikit/uiviewcontroller/1621440-prefersstatusbarhidden?language=objc
     #[inline]
@@ -661,13 +713,17 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// This only has an effect on Windows and X11.
+    /// This only has an effect on Windows, X11 and Web.
     ///
     /// On Windows, this sets `ICON_SMALL`. The base size for a window icon is 16x16, but it's
     /// recommended to account for screen scaling and pick a multiple of that, i.e. 32x32.
     ///
     /// X11 has no universal guidelines for icon sizes, so you're at the whims of the WM. That
     /// said, it's usually in the same ballpark as on Windows.
+    ///
+    /// On Web, this sets the favicon of the page, which is shown in the browser tab. The page has
+    /// a single favicon, so with several windows the icon set last is shown. Passing `None`
+    /// restores the favicon of the page.
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         self.window.set_window_icon(window_icon)
@@ -678,11 +734,40 @@ impl Window {
     /// ## Platform-specific
     ///
     /// **iOS:** Has no effect.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Moves the hidden input element receiving the composition to the position, which
+    ///   the browser shows the candidate box next to.
     #[inline]
     pub fn set_ime_position<P: Into<Position>>(&self, position: P) {
         self.window.set_ime_position(position.into())
     }
+
+    /// Sets whether the window accepts input through an IME, which is the case by default.
+    ///
+    /// While the IME is not allowed, `WindowEvent::Ime` isn't emitted, and text is only received
+    /// through `WindowEvent::ReceivedCharacter`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Keeps the focus on the canvas rather than on the hidden input element receiving
+    ///   the composition.
+    /// - **Windows / macOS / X11 / Wayland / iOS / Android:** Unsupported, has no effect.
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.window.set_ime_allowed(allowed)
+    }
+
+    /// Gives the window keyboard focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Focuses the canvas, which emits `WindowEvent::Focused(true)` unless it already
+    ///   was focused. Has no effect while the canvas is hidden or can't be focused, and some
+    ///   browsers ignore it outside of the handling of user input.
+    /// - **Windows / macOS / X11 / Wayland / iOS / Android:** Unsupported, has no effect.
+    #[inline]
+    pub fn focus_window(&self) {
+        self.window.focus_window()
+    }
 }
 
 /// Cursor functions.
@@ -703,26 +788,33 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Always returns an `Err`.
-    /// - **Web:** Has no effect.
+    /// - **Web:** Only moves the virtual cursor reported while the cursor is grabbed, and returns an
+    ///   `Err` otherwise.
     #[inline]
     pub fn set_cursor_position<P: Into<Position>>(&self, position: P) -> Result<(), ExternalError> {
         self.window.set_cursor_position(position.into())
     }
 
-    /// Grabs the cursor, preventing it from leaving the window.
+    /// Grabs the cursor in the given mode, or releases it with `CursorGrabMode::None`.
+    ///
+    /// Returns `ExternalError::NotSupported` if the platform can't grab the cursor in that mode.
     ///
     /// ## Platform-specific
     ///
-    /// - **macOS:** This presently merely locks the cursor in a fixed location, which looks visually
-    ///   awkward.
-    /// - **Wayland:** This presently merely locks the cursor in a fixed location, which looks visually
-    ///   awkward.
-    /// - **Android:** Has no effect.
-    /// - **iOS:** Always returns an Err.
-    /// - **Web:** Has no effect.
+    /// - **Windows:** Only `Confined` is supported.
+    /// - **X11:** Only `Confined` is supported.
+    /// - **macOS:** Only `Locked` is supported. This presently merely locks the cursor in a fixed
+    ///   location, which looks visually awkward.
+    /// - **Wayland:** Only `Locked` is supported. This presently merely locks the cursor in a fixed
+    ///   location, which looks visually awkward.
+    /// - **Android / iOS:** Always returns an Err.
+    /// - **Web:** Only `Locked` is supported, as browsers can't confine the cursor. It requests a
+    ///   pointer lock on the canvas. While locked, `CursorMoved` positions are accumulated from the
+    ///   raw movement and `DeviceEvent::MouseMotion` is emitted. Losing the lock (e.g. when the
+    ///   user presses Esc) emits `WindowEvent::Focused(false)`, while acquiring it emits nothing.
     #[inline]
-    pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
-        self.window.set_cursor_grab(grab)
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.window.set_cursor_grab(mode)
     }
 
     /// Modifies the cursor's visibility.
@@ -742,6 +834,22 @@ impl Window {
     pub fn set_cursor_visible(&self, visible: bool) {
         self.window.set_cursor_visible(visible)
     }
+
+    /// Moves the window with the left mouse button until the button is released.
+    ///
+    /// There's no guarantee that this will work unless the left mouse button was pressed
+    /// immediately before this function is called.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Moves the canvas, or the frame drawing its decorations, within the page while
+    ///   the pointer is dragged. Fails unless called while handling `WindowEvent::MouseInput` for
+    ///   a press of a mouse button.
+    /// - **Windows / macOS / X11 / Wayland / iOS / Android:** Unsupported.
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        self.window.drag_window()
+    }
 }
 
 /// Monitor info functions.
@@ -792,6 +900,12 @@ unsafe impl raw_window_handle::HasRawWindowHandle for Window {
     }
 }
 
+unsafe impl raw_window_handle::HasRawDisplayHandle for Window {
+    fn raw_display_handle(&self) -> raw_window_handle::RawDisplayHandle {
+        self.window.raw_display_handle()
+    }
+}
+
 /// Describes the appearance of the mouse cursor.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -857,6 +971,26 @@ impl Default for CursorIcon {
     }
 }
 
+/// Describes how the cursor is grabbed by `Window::set_cursor_grab`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CursorGrabMode {
+    /// The cursor is released.
+    None,
+    /// The cursor can move freely, but can't leave the window.
+    Confined,
+    /// The cursor is held in place, and its movement is reported through
+    /// `DeviceEvent::MouseMotion`. On Web, `WindowEvent::CursorMoved` keeps reporting a position
+    /// accumulated from that movement.
+    Locked,
+}
+
+impl Default for CursorGrabMode {
+    fn default() -> Self {
+        CursorGrabMode::None
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Fullscreen {
     Exclusive(VideoMode),
@@ -868,3 +1002,15 @@ pub enum Theme {
     Light,
     Dark,
 }
+
+/// The orientation of a screen.
+///
+/// The primary orientations are the natural ones of the device, and the secondary orientations
+/// are rotated by 180 degrees.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    PortraitPrimary,
+    PortraitSecondary,
+    LandscapePrimary,
+    LandscapeSecondary,
+}
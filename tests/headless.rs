@@ -0,0 +1,62 @@
+#![cfg(feature = "headless")]
+
+use winit::{
+    dpi::PhysicalSize,
+    event::{Event, StartCause, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    platform::{desktop::EventLoopExtDesktop, headless::EventLoopWindowTargetExtHeadless},
+    window::WindowBuilder,
+};
+
+#[test]
+fn pushed_events_drive_control_flow() {
+    let mut event_loop = EventLoop::with_user_event();
+    let window = WindowBuilder::new().build(&event_loop).unwrap();
+    let window_id = window.id();
+
+    event_loop.push_event(Event::WindowEvent {
+        window_id,
+        event: WindowEvent::Resized(PhysicalSize::new(800, 600)),
+    });
+    event_loop.create_proxy().send_event("close").unwrap();
+
+    let mut events = Vec::new();
+    event_loop.run_return(|event, target, control_flow| match event {
+        Event::NewEvents(StartCause::Init) => *control_flow = ControlFlow::Wait,
+        Event::WindowEvent {
+            event: WindowEvent::Resized(size),
+            ..
+        } => {
+            events.push(format!("resized {}x{}", size.width, size.height));
+            window.request_redraw();
+        }
+        Event::UserEvent(message) => {
+            events.push(message.to_owned());
+            target.push_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::CloseRequested,
+            });
+        }
+        Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } => {
+            events.push("close requested".to_owned());
+            *control_flow = ControlFlow::Exit;
+        }
+        Event::RedrawRequested(id) if id == window_id => events.push("redraw".to_owned()),
+        Event::LoopDestroyed => events.push("destroyed".to_owned()),
+        _ => (),
+    });
+
+    assert_eq!(
+        events,
+        [
+            "resized 800x600",
+            "close",
+            "close requested",
+            "redraw",
+            "destroyed"
+        ]
+    );
+}
@@ -0,0 +1,216 @@
+// Runs in a browser, e.g. with `wasm-pack test --headless --firefox --features web-sys,synthetic-events`
+#![cfg(all(
+    target_arch = "wasm32",
+    feature = "web-sys",
+    feature = "synthetic-events"
+))]
+
+use std::{cell::Cell, rc::Rc};
+
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use winit::{
+    dpi::PhysicalSize,
+    event::{Event, StartCause, WindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopClosed},
+    platform::web::{EventLoopExtWebSys, EventLoopWindowTargetExtWebSys, WindowExtWebSys},
+    window::WindowBuilder,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// Redraws are requested from anywhere in an iteration, but only dispatched after
+// `MainEventsCleared`, once per window and in the order the windows were created
+#[wasm_bindgen_test]
+fn redraws_follow_main_events_cleared_once_per_window() {
+    let mut event_loop = EventLoop::new();
+    let first = WindowBuilder::new().build(&event_loop).unwrap();
+    let second = WindowBuilder::new().build(&event_loop).unwrap();
+    event_loop.pump_events(|_, _, _| ());
+
+    event_loop.push_event(Event::WindowEvent {
+        window_id: second.id(),
+        event: WindowEvent::Focused(true),
+    });
+    let mut events = Vec::new();
+    event_loop.pump_events(|event, _, _| match event {
+        Event::WindowEvent {
+            event: WindowEvent::Focused(true),
+            ..
+        } => {
+            events.push("focused".to_owned());
+            second.request_redraw();
+            second.request_redraw();
+        }
+        Event::MainEventsCleared => {
+            events.push("main events cleared".to_owned());
+            first.request_redraw();
+        }
+        Event::RedrawRequested(id) => {
+            let name = if id == first.id() { "first" } else { "second" };
+            events.push(format!("redraw {}", name));
+            // Left for the next call
+            first.request_redraw();
+        }
+        Event::RedrawEventsCleared => events.push("redraw events cleared".to_owned()),
+        _ => (),
+    });
+    assert_eq!(
+        events,
+        [
+            "focused",
+            "main events cleared",
+            "redraw first",
+            "redraw second",
+            "redraw events cleared"
+        ]
+    );
+
+    events.clear();
+    event_loop.pump_events(|event, _, _| match event {
+        Event::MainEventsCleared => events.push("main events cleared".to_owned()),
+        Event::RedrawRequested(id) if id == first.id() => events.push("redraw first".to_owned()),
+        Event::RedrawRequested(_) => events.push("redraw second".to_owned()),
+        Event::RedrawEventsCleared => events.push("redraw events cleared".to_owned()),
+        _ => (),
+    });
+    assert_eq!(
+        events,
+        [
+            "main events cleared",
+            "redraw first",
+            "redraw events cleared"
+        ]
+    );
+}
+
+// The control flow is handed back to the caller instead of being applied, so even `Exit` doesn't
+// stop the event loop from dispatching the events of the next call
+#[wasm_bindgen_test]
+fn pump_events_returns_the_control_flow_without_applying_it() {
+    let mut event_loop = EventLoop::with_user_event();
+    let proxy = event_loop.create_proxy();
+
+    let mut start_causes = Vec::new();
+    let control_flow = event_loop.pump_events(|event, _, control_flow| {
+        if let Event::NewEvents(start_cause) = event {
+            start_causes.push(start_cause);
+            *control_flow = ControlFlow::Wait;
+        }
+    });
+    assert_eq!(control_flow, ControlFlow::Wait);
+
+    let control_flow = event_loop.pump_events(|event, _, control_flow| {
+        if let Event::NewEvents(start_cause) = event {
+            start_causes.push(start_cause);
+            // Each call starts over from `Poll`
+            assert_eq!(*control_flow, ControlFlow::Poll);
+            *control_flow = ControlFlow::Exit;
+        }
+    });
+    assert_eq!(control_flow, ControlFlow::Exit);
+    assert_eq!(start_causes, [StartCause::Init, StartCause::Poll]);
+
+    proxy.send_event("after exit").unwrap();
+    let mut user_events = Vec::new();
+    let mut destroyed = false;
+    event_loop.pump_events(|event, _, _| match event {
+        Event::UserEvent(message) => user_events.push(message),
+        Event::LoopDestroyed => destroyed = true,
+        _ => (),
+    });
+    assert_eq!(user_events, ["after exit"]);
+    assert!(!destroyed);
+}
+
+// Once a running event loop has exited, proxies hand their events back
+#[wasm_bindgen_test]
+fn proxy_hands_events_back_once_the_event_loop_exited() {
+    let event_loop = EventLoop::with_user_event();
+    let proxy = event_loop.create_proxy();
+    let exited = Rc::new(Cell::new(false));
+
+    let on_exit = {
+        let exited = exited.clone();
+        move || exited.set(true)
+    };
+    event_loop.run_with_exit(
+        |event, _, control_flow| {
+            if let Event::Resumed = event {
+                *control_flow = ControlFlow::Exit;
+            }
+        },
+        on_exit,
+    );
+
+    assert!(exited.get());
+    assert_eq!(proxy.send_event(1), Err(EventLoopClosed(1)));
+}
+
+#[wasm_bindgen_test]
+fn scale_factor_changed_applies_the_size_set_by_the_handler() {
+    let mut event_loop = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_inner_size(PhysicalSize::new(400, 300))
+        .build(&event_loop)
+        .unwrap();
+    event_loop.pump_events(|_, _, _| ());
+
+    // Without a size set by the handler, the suggested one keeps the size on the page
+    window.set_scale_factor_override(Some(2.0));
+    let mut suggested = None;
+    event_loop.pump_events(|event, _, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ScaleFactorChanged { new_inner_size, .. },
+            ..
+        } = event
+        {
+            suggested = Some(new_inner_size);
+        }
+    });
+    assert_eq!(suggested, Some(PhysicalSize::new(800, 600)));
+    assert_eq!(window.inner_size(), PhysicalSize::new(800, 600));
+
+    // The size set while the event is handled replaces the suggested one once the handler returns
+    window.set_scale_factor_override(Some(1.0));
+    event_loop.pump_events(|event, _, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ScaleFactorChanged { .. },
+            ..
+        } = event
+        {
+            window.set_inner_size(PhysicalSize::new(500, 500));
+            assert_eq!(window.inner_size(), PhysicalSize::new(800, 600));
+        }
+    });
+    assert_eq!(window.inner_size(), PhysicalSize::new(500, 500));
+}
+
+// Only the events sent through proxies count towards the bound of the queue
+#[wasm_bindgen_test]
+fn user_events_are_bounded_apart_from_other_events() {
+    let mut event_loop = EventLoop::with_user_event();
+    let window = WindowBuilder::new().build(&event_loop).unwrap();
+    for _ in 0..5000 {
+        event_loop.push_event(Event::WindowEvent {
+            window_id: window.id(),
+            event: WindowEvent::Focused(true),
+        });
+    }
+
+    let proxy = event_loop.create_proxy();
+    for i in 0..4096 {
+        proxy.send_event(i).unwrap();
+    }
+    assert_eq!(proxy.send_event(4096), Err(EventLoopClosed(4096)));
+
+    let mut user_events = Vec::new();
+    event_loop.pump_events(|event, _, _| {
+        if let Event::UserEvent(i) = event {
+            user_events.push(i);
+        }
+    });
+    assert!(user_events.iter().copied().eq(0..4096));
+
+    // Handled events make room for new ones
+    assert_eq!(proxy.send_event(4096), Ok(()));
+}